@@ -1,13 +1,26 @@
 pub(crate) mod metrics;
 mod queries;
 
+use crate::queries::areas::areas;
+use crate::queries::days::days;
 use crate::queries::now_and_next::now_and_next;
+use crate::queries::rejected::rejected;
 use crate::queries::schedule::schedule;
+use crate::queries::sessions::sessions;
+use crate::queries::speakers::{speaker, speakers};
+use crate::queries::validation::validation;
 use crate::queries::venues::venues;
+use crate::queries::venues_geojson::venues_geojson;
 use anyhow::Result;
 use axum::{Router, routing::get};
+use chrono_tz::Tz;
 use clap::Parser;
-use std::net::SocketAddr;
+use emfcamp_schedule_api::schedule::{
+    decode::Decoding,
+    mutation::{CanonicalVenues, MutatorSpec, Mutators, Pipeline},
+    venue::VenueRegistry,
+};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 use tokio::net::TcpListener;
 use tracing::{info, trace};
 use url::Url;
@@ -22,6 +35,38 @@ struct Cli {
     )]
     upstream_api_url: Url,
 
+    /// Time in seconds for which a schedule retrieved from the upstream API is reused without
+    /// making another request
+    #[clap(long, env, default_value = "0")]
+    upstream_max_age: u64,
+
+    /// Time in seconds after which a request to the upstream API is abandoned
+    #[clap(long, env, default_value = "10")]
+    upstream_timeout: u64,
+
+    /// Number of times a request to the upstream API that fails to connect or gets a server
+    /// error is retried
+    #[clap(long, env, default_value = "2")]
+    upstream_retries: u32,
+
+    /// Skip events from the upstream API that cannot be decoded instead of failing the request
+    #[clap(long, env)]
+    lenient: bool,
+
+    /// Timezone in which the upstream API gives times that have no offset
+    #[clap(long, env, default_value = "Europe/London")]
+    timezone: Tz,
+
+    /// Mutators applied to the schedule before handling each request, read once at startup from
+    /// a JSON or TOML (.toml) file
+    #[clap(long, env)]
+    mutators: Option<PathBuf>,
+
+    /// Venue registry (JSON, or TOML with a .toml extension) used to serve canonical venue names
+    /// and the areas and venue order given by the /areas and /now-and-next endpoints
+    #[clap(long, env)]
+    venue_registry: Option<PathBuf>,
+
     #[clap(long, env, default_value = "127.0.0.1:8000")]
     api_address: SocketAddr,
 
@@ -32,6 +77,8 @@ struct Cli {
 #[derive(Clone)]
 struct State {
     client: emfcamp_schedule_api::Client,
+    mutators: Arc<Mutators>,
+    venues: Arc<VenueRegistry>,
 }
 
 #[tokio::main]
@@ -43,14 +90,56 @@ async fn main() -> Result<()> {
     crate::metrics::init(args.observability_address)?;
 
     trace!("Creating client");
-    let client = emfcamp_schedule_api::Client::new(args.upstream_api_url);
+    let client = emfcamp_schedule_api::Client::builder(args.upstream_api_url)
+        .application(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+        .timeout(Duration::from_secs(args.upstream_timeout))
+        .retries(args.upstream_retries)
+        .max_age(Duration::from_secs(args.upstream_max_age))
+        .decoding(if args.lenient {
+            Decoding::Lenient
+        } else {
+            Decoding::Strict
+        })
+        .timezone(args.timezone)
+        .build()?;
+
+    let venue_registry = match args.venue_registry {
+        Some(path) => VenueRegistry::from_file(path)?,
+        None => VenueRegistry::default(),
+    };
+    info!("Known venues: {}", venue_registry.venues.len());
+
+    let mut pipeline = match args.mutators {
+        Some(path) => Pipeline::from_file(path)?,
+        None => Pipeline::default(),
+    };
+    if !venue_registry.venues.is_empty() {
+        let canonical_venues = CanonicalVenues::new(venue_registry.clone());
+        pipeline
+            .mutators
+            .insert(0, MutatorSpec::CanonicalVenues(canonical_venues));
+    }
+    let mutators: Mutators = pipeline.into();
+    info!("Mutators: {mutators:?}");
 
-    let state = State { client };
+    let state = State {
+        client,
+        mutators: Arc::new(mutators),
+        venues: Arc::new(venue_registry),
+    };
 
     let app = Router::new()
         .route("/schedule", get(schedule))
+        .route("/schedule/rejected", get(rejected))
         .route("/now-and-next", get(now_and_next))
         .route("/venues", get(venues))
+        .route("/areas", get(areas))
+        .route("/venues.geojson", get(venues_geojson))
+        .route("/days", get(days))
+        .route("/sessions", get(sessions))
+        .route("/speakers", get(speakers))
+        .route("/speakers/:name", get(speaker))
+        .route("/validation", get(validation))
         .with_state(state);
 
     info!("API shim running at {}", args.api_address);
@@ -8,6 +8,7 @@ pub(crate) static REQUESTS: &str = "emf_schedule_adapter_requests_total";
 pub(crate) static ENDPOINT_LABEL: &str = "endpoint";
 
 pub(crate) static UPSTREAM_API_FAILURES: &str = "emf_schedule_adapter_upstream_api_failures_total";
+pub(crate) static REASON_LABEL: &str = "reason";
 
 pub(super) fn init(address: SocketAddr) -> Result<(), BuildError> {
     info!("Starting observability server on {address}");
@@ -0,0 +1,23 @@
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use metrics::counter;
+use tracing::info;
+
+#[axum::debug_handler]
+pub(crate) async fn areas(State(state): State<crate::State>) -> Response {
+    info!("Query: areas");
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "areas").increment(1);
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            let areas = schedule.areas(&state.venues);
+            Json(areas).into_response()
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
@@ -0,0 +1,55 @@
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::Query;
+use chrono::{NaiveDate, Weekday};
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct DaysQueryParams {
+    /// Hour of the morning at which one festival day ends and the next begins.
+    rollover_hour: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct Day {
+    date: NaiveDate,
+    weekday: Weekday,
+    events: usize,
+}
+
+#[axum::debug_handler]
+pub(crate) async fn days(
+    State(state): State<crate::State>,
+    Query(query): Query<DaysQueryParams>,
+) -> Response {
+    info!("Query: days: {:?}", query);
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "days").increment(1);
+
+    let rollover_hour = match super::rollover_hour(query.rollover_hour) {
+        Ok(rollover_hour) => rollover_hour,
+        Err(err) => return super::bad_query_response(err),
+    };
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            let days: Vec<_> = schedule
+                .events_by_day(rollover_hour)
+                .into_iter()
+                .map(|(day, events)| Day {
+                    date: day.date(),
+                    weekday: day.weekday(),
+                    events: events.len(),
+                })
+                .collect();
+            Json(days).into_response()
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
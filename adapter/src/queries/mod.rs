@@ -1,3 +1,161 @@
+pub(crate) mod areas;
+pub(crate) mod days;
 pub(crate) mod now_and_next;
+pub(crate) mod rejected;
 pub(crate) mod schedule;
+pub(crate) mod sessions;
+pub(crate) mod speakers;
+pub(crate) mod validation;
 pub(crate) mod venues;
+pub(crate) mod venues_geojson;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, FixedOffset};
+use emfcamp_schedule_api::{
+    Error,
+    schedule::{
+        day::DEFAULT_ROLLOVER_HOUR,
+        mutation,
+        query::{self, ParseError},
+    },
+};
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, OneOrMany, serde_as};
+use std::fmt;
+use tracing::{error, info};
+
+/// Parameters that filter the events of the schedule, shared by the endpoints that return events.
+///
+/// These are flattened into the parameters of each endpoint, which means they are seen as strings
+/// (or sequences of strings) rather than parsed by the query string deserializer itself.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct FilterQueryParams {
+    /// Offset the timestamps of all events, using this as the starting time of the earliest event.
+    /// For development use.
+    fake_epoch: Option<DateTime<FixedOffset>>,
+
+    /// When using `fake_epoch`, also run the schedule this many times faster than real time.
+    /// For development use.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    fake_time_scale: Option<f64>,
+
+    /// Include only events that take place at these venues.
+    #[serde(rename = "venue")]
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    venues: Option<Vec<String>>,
+
+    /// Include only events that match this query, see [`query::Query`] for the syntax.
+    query: Option<String>,
+
+    /// Hour of the morning at which one festival day ends and the next begins, for `day:` terms
+    /// in `query` (and anything else about festival days the endpoint has).
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    rollover_hour: Option<u32>,
+}
+
+impl TryFrom<FilterQueryParams> for mutation::Mutators {
+    type Error = BadQuery;
+
+    fn try_from(params: FilterQueryParams) -> Result<Self, Self::Error> {
+        let mut mutators = Self::new_single(Box::<mutation::SortedByStartTime>::default());
+        let rollover_hour = rollover_hour(params.rollover_hour)?;
+
+        if let Some(epoch) = params.fake_epoch {
+            let mut mutator = mutation::FakeStartEpoch::new(epoch);
+            if let Some(time_scale) = params.fake_time_scale {
+                mutator = mutator.with_time_scale(time_scale.try_into()?);
+            }
+            mutators.push(Box::new(mutator));
+        }
+
+        if let Some(q) = params.query {
+            mutators.push(Box::new(query::MatchesQuery::new(
+                query::Query::parse(&q)?,
+                rollover_hour,
+            )));
+        }
+
+        if let Some(venues) = params.venues {
+            mutators.push(Box::new(mutation::AtVenues::new(venues)));
+        }
+
+        Ok(mutators)
+    }
+}
+
+/// A problem with the parameters of a request.
+#[derive(Debug)]
+pub(crate) enum BadQuery {
+    Query(ParseError),
+    RolloverHour(u32),
+    TimeScale(mutation::InvalidTimeScale),
+}
+
+impl fmt::Display for BadQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BadQuery::Query(err) => write!(f, "{err}"),
+            BadQuery::RolloverHour(hour) => {
+                write!(f, "invalid rollover hour `{hour}`, expected 0 to 23")
+            }
+            BadQuery::TimeScale(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<ParseError> for BadQuery {
+    fn from(err: ParseError) -> Self {
+        BadQuery::Query(err)
+    }
+}
+
+impl From<mutation::InvalidTimeScale> for BadQuery {
+    fn from(err: mutation::InvalidTimeScale) -> Self {
+        BadQuery::TimeScale(err)
+    }
+}
+
+/// Checks the hour of the morning at which one festival day ends and the next begins, if one was
+/// given, otherwise using the default.
+pub(crate) fn rollover_hour(rollover_hour: Option<u32>) -> Result<u32, BadQuery> {
+    match rollover_hour {
+        None => Ok(DEFAULT_ROLLOVER_HOUR),
+        Some(hour) if hour < 24 => Ok(hour),
+        Some(hour) => Err(BadQuery::RolloverHour(hour)),
+    }
+}
+
+/// Builds the response for a failed request to the upstream API, choosing a status code that
+/// reflects the cause of the failure.
+pub(crate) fn upstream_error_response(err: Error) -> Response {
+    error!("{err}");
+
+    let (status, reason) = match &err {
+        Error::HttpError(e) if e.is_timeout() => (StatusCode::GATEWAY_TIMEOUT, "timeout"),
+        Error::HttpError(_) => (StatusCode::BAD_GATEWAY, "http"),
+        Error::HttpStatus(_) => (StatusCode::BAD_GATEWAY, "status"),
+        Error::JsonError(_) | Error::EventDecodeError { .. } => (StatusCode::BAD_GATEWAY, "decode"),
+        Error::TimestampParseError { .. } => (StatusCode::BAD_GATEWAY, "timestamp"),
+        Error::IoError(_)
+        | Error::NotAFileUrl(_)
+        | Error::InvalidHeaderValue(_)
+        | Error::TomlError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "local"),
+    };
+
+    counter!(crate::metrics::UPSTREAM_API_FAILURES, crate::metrics::REASON_LABEL => reason)
+        .increment(1);
+
+    status.into_response()
+}
+
+/// Builds the response for a request with a query that could not be parsed, or other parameters
+/// that could not be used.
+pub(crate) fn bad_query_response(err: BadQuery) -> Response {
+    info!("Bad query: {err}");
+    (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+}
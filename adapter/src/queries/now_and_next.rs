@@ -1,45 +1,41 @@
 use axum::{
     Json,
     extract::State,
-    http::StatusCode,
     response::{IntoResponse, Response},
 };
 use axum_extra::extract::Query;
-use chrono::{DateTime, FixedOffset, Local};
-use emfcamp_schedule_api::schedule::mutation;
+use chrono::{DateTime, Duration, FixedOffset, Local};
+use emfcamp_schedule_api::schedule::now_and_next::VenueOrder;
 use metrics::counter;
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use tracing::{debug, info};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct NowAndNextQueryParams {
-    /// Offset the timestamps of all events, using this as the starting time of the earliest event.
-    /// For development use.
-    fake_epoch: Option<DateTime<FixedOffset>>,
+    #[serde(flatten)]
+    filter: super::FilterQueryParams,
 
     /// Use this time instead of the actual currrent time when evaluating events against time based filters.
     /// For development use.
     now: Option<DateTime<FixedOffset>>,
 
-    /// Include only events that take place at these venues.
-    #[serde(rename = "venue")]
-    venues: Option<Vec<String>>,
-}
+    /// Also list events later than the next ones, up to this many start times at each venue
+    /// (including that of the next events).
+    slots: Option<usize>,
 
-impl From<NowAndNextQueryParams> for mutation::Mutators {
-    fn from(params: NowAndNextQueryParams) -> Self {
-        let mut mutators = Self::new_single(Box::<mutation::SortedByStartTime>::default());
+    /// Also list events later than the next ones, up to this many upcoming events at each venue
+    /// (including the next events).
+    events: Option<usize>,
 
-        if let Some(epoch) = params.fake_epoch {
-            mutators.push(Box::new(mutation::FakeStartEpoch::new(epoch)));
-        }
+    /// Also list events later than the next ones that start within this many minutes.
+    horizon_minutes: Option<u32>,
 
-        if let Some(venues) = params.venues {
-            mutators.push(Box::new(mutation::AtVenues::new(venues)));
-        }
+    /// How to order the venues.
+    order: Option<VenueOrder>,
 
-        mutators
-    }
+    /// Venues to list first when ordering by priority, before the order of the venue registry.
+    #[serde(rename = "priority")]
+    priorities: Option<Vec<String>>,
 }
 
 #[axum::debug_handler]
@@ -51,20 +47,44 @@ pub(crate) async fn now_and_next(
     counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "now_and_next")
         .increment(1);
 
+    let now = query.now.unwrap_or_else(|| Local::now().into());
+    let (slots, events, horizon_minutes) = (query.slots, query.events, query.horizon_minutes);
+    let rollover_hour = match super::rollover_hour(query.filter.rollover_hour) {
+        Ok(rollover_hour) => rollover_hour,
+        Err(err) => return super::bad_query_response(err),
+    };
+    let order = query.order.unwrap_or_default();
+    let priorities = query.priorities.clone().unwrap_or_default();
+
+    let mutators = match query.filter.try_into() {
+        Ok(mutators) => mutators,
+        Err(err) => return super::bad_query_response(err),
+    };
+    debug!("Mutators: {mutators:?}");
+
     match state.client.get_schedule().await {
         Ok(mut schedule) => {
-            let now = query.now.unwrap_or_else(|| Local::now().into());
-
-            let mutators = query.into();
+            schedule.mutate(&state.mutators);
             schedule.mutate(&mutators);
 
-            let epg = schedule.now_and_next(now);
+            let mut epg = schedule
+                .now_and_next_builder(now)
+                .rollover_hour(rollover_hour)
+                .order(order)
+                .priorities(priorities)
+                .venue_registry(&state.venues);
+            if let Some(slots) = slots {
+                epg = epg.slots(slots);
+            }
+            if let Some(events) = events {
+                epg = epg.events(events);
+            }
+            if let Some(minutes) = horizon_minutes {
+                epg = epg.horizon(Duration::minutes(minutes.into()));
+            }
+            let epg = epg.build();
             Json(epg).into_response()
         }
-        Err(err) => {
-            error!("{err}");
-            counter!(crate::metrics::UPSTREAM_API_FAILURES).increment(1);
-            (StatusCode::INTERNAL_SERVER_ERROR).into_response()
-        }
+        Err(err) => super::upstream_error_response(err),
     }
 }
@@ -0,0 +1,18 @@
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use metrics::counter;
+use tracing::info;
+
+#[axum::debug_handler]
+pub(crate) async fn rejected(State(state): State<crate::State>) -> Response {
+    info!("Query: rejected");
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "rejected").increment(1);
+
+    match state.client.get_decoded_schedule().await {
+        Ok(decoded) => Json(decoded.rejected).into_response(),
+        Err(err) => super::upstream_error_response(err),
+    }
+}
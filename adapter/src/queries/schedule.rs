@@ -1,21 +1,19 @@
 use axum::{
     Json,
     extract::State,
-    http::StatusCode,
     response::{IntoResponse, Response},
 };
 use axum_extra::extract::Query;
 use chrono::{DateTime, FixedOffset};
-use emfcamp_schedule_api::schedule::mutation;
+use emfcamp_schedule_api::schedule::{day::DaySelector, markdown, mutation};
 use metrics::counter;
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use tracing::{debug, info};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct ScheduleQueryParams {
-    /// Offset the timestamps of all events, using this as the starting time of the earliest event.
-    /// For development use.
-    fake_epoch: Option<DateTime<FixedOffset>>,
+    #[serde(flatten)]
+    filter: super::FilterQueryParams,
 
     /// Include only events that start after this time.
     starting_after: Option<DateTime<FixedOffset>>,
@@ -26,18 +24,45 @@ pub(crate) struct ScheduleQueryParams {
     /// Include only events that end after this time.
     ending_after: Option<DateTime<FixedOffset>>,
 
-    /// Include only events that take place at these venues.
-    #[serde(rename = "venue")]
-    venues: Option<Vec<String>>,
+    /// Include only events that start on this festival day (e.g. `saturday`, `sat` or `2024-06-01`).
+    day: Option<DaySelector>,
+
+    /// Include only events with titles, speakers or descriptions that match this full text search.
+    search: Option<String>,
+
+    /// How to present event descriptions, which are markdown by default.
+    description_format: Option<DescriptionFormat>,
 }
 
-impl From<ScheduleQueryParams> for mutation::Mutators {
-    fn from(params: ScheduleQueryParams) -> Self {
-        let mut mutators = Self::new_single(Box::<mutation::SortedByStartTime>::default());
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DescriptionFormat {
+    /// As given by the upstream API.
+    Markdown,
 
-        if let Some(epoch) = params.fake_epoch {
-            mutators.push(Box::new(mutation::FakeStartEpoch::new(epoch)));
+    /// Plain text, without any markup.
+    Text,
+
+    /// Sanitised HTML.
+    Html,
+}
+
+impl DescriptionFormat {
+    fn render(self, description: &str) -> String {
+        match self {
+            Self::Markdown => description.to_owned(),
+            Self::Text => markdown::to_plain_text(description),
+            Self::Html => markdown::to_html(description),
         }
+    }
+}
+
+impl TryFrom<ScheduleQueryParams> for mutation::Mutators {
+    type Error = super::BadQuery;
+
+    fn try_from(params: ScheduleQueryParams) -> Result<Self, Self::Error> {
+        let rollover_hour = super::rollover_hour(params.filter.rollover_hour)?;
+        let mut mutators: Self = params.filter.try_into()?;
 
         if let Some(starting_after) = params.starting_after {
             mutators.push(Box::new(mutation::StartsAfter::new(starting_after)));
@@ -51,11 +76,15 @@ impl From<ScheduleQueryParams> for mutation::Mutators {
             mutators.push(Box::new(mutation::EndsAfter::new(ending_after)));
         }
 
-        if let Some(venues) = params.venues {
-            mutators.push(Box::new(mutation::AtVenues::new(venues)));
+        if let Some(day) = params.day {
+            mutators.push(Box::new(mutation::OnDay::new(day, rollover_hour)));
+        }
+
+        if let Some(search) = params.search {
+            mutators.push(Box::new(mutation::MatchesSearch::new(search)));
         }
 
-        mutators
+        Ok(mutators)
     }
 }
 
@@ -67,18 +96,26 @@ pub(crate) async fn schedule(
     info!("Query: schedule: {:?}", query);
     counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "schedule").increment(1);
 
+    let description_format = query.description_format;
+    let mutators = match query.try_into() {
+        Ok(mutators) => mutators,
+        Err(err) => return super::bad_query_response(err),
+    };
+    debug!("Mutators: {mutators:?}");
+
     match state.client.get_schedule().await {
         Ok(mut schedule) => {
-            let mutators = query.into();
+            schedule.mutate(&state.mutators);
             schedule.mutate(&mutators);
 
             let events = &mut schedule.events;
+            if let Some(format) = description_format {
+                for event in events.iter_mut() {
+                    event.description = format.render(&event.description);
+                }
+            }
             Json(events).into_response()
         }
-        Err(err) => {
-            error!("{err}");
-            counter!(crate::metrics::UPSTREAM_API_FAILURES).increment(1);
-            (StatusCode::INTERNAL_SERVER_ERROR).into_response()
-        }
+        Err(err) => super::upstream_error_response(err),
     }
 }
@@ -0,0 +1,38 @@
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::Query;
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct SessionsQueryParams {
+    /// Include only events that run more than once.
+    #[serde(default)]
+    repeated: bool,
+}
+
+#[axum::debug_handler]
+pub(crate) async fn sessions(
+    State(state): State<crate::State>,
+    Query(query): Query<SessionsQueryParams>,
+) -> Response {
+    info!("Query: sessions: {:?}", query);
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "sessions").increment(1);
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            let mut groups = schedule.session_groups();
+            if query.repeated {
+                groups.retain(|group| group.is_repeated());
+            }
+            Json(groups).into_response()
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
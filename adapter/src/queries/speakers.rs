@@ -0,0 +1,64 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use metrics::counter;
+use serde::Serialize;
+use tracing::info;
+
+#[derive(Debug, Serialize)]
+struct SpeakerSummary {
+    name: String,
+    pronouns: Option<String>,
+    events: Vec<u32>,
+}
+
+#[axum::debug_handler]
+pub(crate) async fn speakers(State(state): State<crate::State>) -> Response {
+    info!("Query: speakers");
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "speakers").increment(1);
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            let speakers: Vec<_> = schedule
+                .speakers()
+                .into_iter()
+                .map(|speaker| SpeakerSummary {
+                    name: speaker.name,
+                    pronouns: speaker.pronouns,
+                    events: speaker.events.iter().map(|e| e.id).collect(),
+                })
+                .collect();
+            Json(speakers).into_response()
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
+
+#[axum::debug_handler]
+pub(crate) async fn speaker(
+    State(state): State<crate::State>,
+    Path(name): Path<String>,
+) -> Response {
+    info!("Query: speaker: {name}");
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "speaker").increment(1);
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            match schedule.speaker(&name) {
+                Some(mut speaker) => {
+                    speaker.events.sort();
+                    Json(speaker).into_response()
+                }
+                None => StatusCode::NOT_FOUND.into_response(),
+            }
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
@@ -1,11 +1,10 @@
 use axum::{
     Json,
     extract::State,
-    http::StatusCode,
     response::{IntoResponse, Response},
 };
 use metrics::counter;
-use tracing::{error, info};
+use tracing::info;
 
 #[axum::debug_handler]
 pub(crate) async fn venues(State(state): State<crate::State>) -> Response {
@@ -13,14 +12,12 @@ pub(crate) async fn venues(State(state): State<crate::State>) -> Response {
     counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "venues").increment(1);
 
     match state.client.get_schedule().await {
-        Ok(schedule) => {
-            let venues = schedule.venues();
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+
+            let venues = schedule.canonical_venues(&state.venues);
             Json(venues).into_response()
         }
-        Err(err) => {
-            error!("{err}");
-            counter!(crate::metrics::UPSTREAM_API_FAILURES).increment(1);
-            (StatusCode::INTERNAL_SERVER_ERROR).into_response()
-        }
+        Err(err) => super::upstream_error_response(err),
     }
 }
@@ -0,0 +1,55 @@
+use axum::{
+    Json,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::Query;
+use chrono::{DateTime, FixedOffset, Local};
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct VenuesGeoJsonQueryParams {
+    #[serde(flatten)]
+    filter: super::FilterQueryParams,
+
+    /// Use this time instead of the actual currrent time when evaluating events against time based filters.
+    /// For development use.
+    now: Option<DateTime<FixedOffset>>,
+}
+
+/// The venues with known positions as GeoJSON points, each with what is on now and next there.
+#[axum::debug_handler]
+pub(crate) async fn venues_geojson(
+    State(state): State<crate::State>,
+    Query(query): Query<VenuesGeoJsonQueryParams>,
+) -> Response {
+    info!("Query: venues GeoJSON: {:?}", query);
+    counter!(crate::metrics::REQUESTS, crate::metrics::ENDPOINT_LABEL => "venues_geojson")
+        .increment(1);
+
+    let now = query.now.unwrap_or_else(|| Local::now().into());
+
+    let mutators = match query.filter.try_into() {
+        Ok(mutators) => mutators,
+        Err(err) => return super::bad_query_response(err),
+    };
+    debug!("Mutators: {mutators:?}");
+
+    match state.client.get_schedule().await {
+        Ok(mut schedule) => {
+            schedule.mutate(&state.mutators);
+            schedule.mutate(&mutators);
+
+            let geojson = schedule.venues_geojson(now);
+            (
+                [(header::CONTENT_TYPE, "application/geo+json")],
+                Json(geojson),
+            )
+                .into_response()
+        }
+        Err(err) => super::upstream_error_response(err),
+    }
+}
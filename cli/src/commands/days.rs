@@ -0,0 +1,15 @@
+use super::RolloverCommonOptions;
+use clap::Parser;
+use emfcamp_schedule_api::schedule::Schedule;
+
+#[derive(Debug, Parser)]
+pub(crate) struct DaysOptions {
+    #[clap(flatten)]
+    rollover: RolloverCommonOptions,
+}
+
+pub(crate) fn run(args: DaysOptions, schedule: Schedule) {
+    for (day, events) in schedule.events_by_day(args.rollover.rollover_hour) {
+        println!("{day} ({} events)", events.len());
+    }
+}
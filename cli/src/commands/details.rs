@@ -1,5 +1,5 @@
 use clap::Parser;
-use emfcamp_schedule_api::schedule::{Schedule, event::Event};
+use emfcamp_schedule_api::schedule::{Schedule, event::Event, markdown};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 #[derive(Debug, Parser)]
@@ -15,7 +15,10 @@ pub(crate) fn run(args: EventDetailsOptions, schedule: Schedule) {
     let mut stdout = StandardStream::stdout(args.color);
 
     match schedule.events.iter().find(|event| event.id == args.event) {
-        Some(event) => print_verbose_event_details(&mut stdout, event),
+        Some(event) => {
+            print_verbose_event_details(&mut stdout, event);
+            print_other_sessions(&mut stdout, &schedule.other_sessions(event));
+        }
         None => println!("Failed to find event with ID {}", args.event),
     }
 }
@@ -64,5 +67,29 @@ fn print_verbose_event_details(stdout: &mut StandardStream, event: &Event) {
     stdout.set_color(&ColorSpec::default()).unwrap();
     println!();
 
-    println!("{}", event.description);
+    markdown::write_styled(stdout, &event.description).unwrap();
+    stdout.reset().unwrap();
+    println!();
+}
+
+fn print_other_sessions(stdout: &mut StandardStream, sessions: &[Event]) {
+    if sessions.is_empty() {
+        return;
+    }
+
+    stdout
+        .set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))
+        .unwrap();
+    println!();
+    println!("Also running at:");
+    for event in sessions {
+        println!(
+            "  {} - {} : {} (ID {})",
+            event.start.format("%a %H:%M"),
+            event.end.format("%H:%M"),
+            event.venue,
+            event.id
+        );
+    }
+    stdout.reset().unwrap();
 }
@@ -1,5 +1,6 @@
-use super::{ListingTableCommonOptions, VenueFilterCommonOptions};
-use crate::formatting::event_listing;
+use super::{
+    DayFilterCommonOptions, ListingTableCommonOptions, QueryCommonOptions, VenueFilterCommonOptions,
+};
 use clap::Parser;
 use emfcamp_schedule_api::schedule::{
     Schedule,
@@ -13,11 +14,24 @@ pub(crate) struct FullOptions {
 
     #[clap(flatten)]
     venues: VenueFilterCommonOptions,
+
+    #[clap(flatten)]
+    day: DayFilterCommonOptions,
+
+    #[clap(flatten)]
+    query: QueryCommonOptions,
 }
 
 pub(crate) fn run(args: FullOptions, mut schedule: Schedule) {
     let mut mutations = Mutators::default();
 
+    // Filter by query if requested
+    args.query
+        .push_mutators(&mut mutations, args.day.rollover.rollover_hour);
+
+    // Filter by festival day if requested
+    args.day.push_mutators(&mut mutations);
+
     // Filter by venue if requested
     if let Some(venues) = args.venues.venues {
         mutations.push(Box::new(mutation::AtVenues::new(venues)));
@@ -27,7 +41,5 @@ pub(crate) fn run(args: FullOptions, mut schedule: Schedule) {
     mutations.push(Box::<SortedByStartTime>::default());
 
     schedule.mutate(&mutations);
-    let events = schedule.events;
-
-    event_listing::print_table(args.table.width.max_width, &args.table.columns, &events);
+    args.table.print(schedule.events);
 }
@@ -1,12 +1,23 @@
+pub(super) mod days;
 pub(super) mod details;
 pub(super) mod full;
 pub(super) mod now_next;
+pub(super) mod search;
+pub(super) mod speakers;
 pub(super) mod upcoming;
+pub(super) mod validate;
 pub(super) mod venues;
 
 use crate::formatting::event_listing;
 use chrono::{DateTime, FixedOffset};
 use clap::Parser;
+use emfcamp_schedule_api::schedule::{
+    Schedule,
+    day::{DEFAULT_ROLLOVER_HOUR, DaySelector},
+    event::Event,
+    mutation::{Mutators, OnDay},
+    query::{MatchesQuery, Query},
+};
 
 #[derive(Debug, Parser)]
 pub(super) struct TableWidthCommonOptions {
@@ -23,6 +34,21 @@ pub(super) struct ListingTableCommonOptions {
     /// Only show specific columns
     #[clap(short, long, value_enum, default_values_t = event_listing::default_columns())]
     columns: Vec<event_listing::Column>,
+
+    /// Show events that run more than once (e.g. repeated workshops) as a single row
+    #[clap(long)]
+    group_repeats: bool,
+}
+
+impl ListingTableCommonOptions {
+    fn print(&self, events: Vec<Event>) {
+        if self.group_repeats {
+            let groups = Schedule { events }.session_groups();
+            event_listing::print_grouped_table(self.width.max_width, &self.columns, &groups);
+        } else {
+            event_listing::print_table(self.width.max_width, &self.columns, &events);
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -32,6 +58,52 @@ pub(super) struct VenueFilterCommonOptions {
     venues: Option<Vec<String>>,
 }
 
+#[derive(Debug, Parser)]
+pub(super) struct RolloverCommonOptions {
+    /// Hour of the morning at which one festival day ends and the next begins
+    #[clap(
+        long,
+        value_name = "HOUR",
+        default_value_t = DEFAULT_ROLLOVER_HOUR,
+        value_parser = clap::value_parser!(u32).range(0..24)
+    )]
+    rollover_hour: u32,
+}
+
+#[derive(Debug, Parser)]
+pub(super) struct DayFilterCommonOptions {
+    /// Only show events on a festival day (e.g. `saturday`, `sat` or `2024-06-01`)
+    #[clap(short, long)]
+    day: Option<DaySelector>,
+
+    #[clap(flatten)]
+    rollover: RolloverCommonOptions,
+}
+
+impl DayFilterCommonOptions {
+    fn push_mutators(self, mutations: &mut Mutators) {
+        if let Some(day) = self.day {
+            mutations.push(Box::new(OnDay::new(day, self.rollover.rollover_hour)));
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub(super) struct QueryCommonOptions {
+    /// Only show events matching a query (e.g. `kind:workshop start>=sat10:00 family_friendly`)
+    #[clap(short, long)]
+    query: Option<Query>,
+}
+
+impl QueryCommonOptions {
+    /// Adds the query, with `day:` terms ending each day at `rollover_hour`.
+    fn push_mutators(self, mutations: &mut Mutators, rollover_hour: u32) {
+        if let Some(query) = self.query {
+            mutations.push(Box::new(MatchesQuery::new(query, rollover_hour)));
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 pub(super) struct NowCommonOptions {
     /// Manually specify the time to consider "now" (mainly used for debugging)
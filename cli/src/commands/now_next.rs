@@ -1,10 +1,16 @@
-use super::{NowCommonOptions, TableWidthCommonOptions, VenueFilterCommonOptions};
+use super::{
+    NowCommonOptions, QueryCommonOptions, RolloverCommonOptions, TableWidthCommonOptions,
+    VenueFilterCommonOptions,
+};
 use ascii_table::{AsciiTable, Width};
+use chrono::Duration;
 use clap::Parser;
 use emfcamp_schedule_api::schedule::{
     Schedule,
     event::Event,
     mutation::{self, Mutators, SortedByStartTime},
+    now_and_next::{VenueNowAndNext, VenueOrder},
+    venue::VenueRegistry,
 };
 
 #[derive(Debug, Parser)]
@@ -15,13 +21,45 @@ pub(crate) struct NowNextOptions {
     #[clap(flatten)]
     venues: VenueFilterCommonOptions,
 
+    #[clap(flatten)]
+    query: QueryCommonOptions,
+
     #[clap(flatten)]
     now: NowCommonOptions,
+
+    #[clap(flatten)]
+    rollover: RolloverCommonOptions,
+
+    /// Also show events later than the next ones, up to this many start times at each venue
+    /// (including that of the next events)
+    #[clap(long, value_name = "COUNT")]
+    slots: Option<usize>,
+
+    /// Also show events later than the next ones, up to this many upcoming events at each venue
+    /// (including the next events)
+    #[clap(long, value_name = "COUNT")]
+    events: Option<usize>,
+
+    /// Also show events later than the next ones that start within this many minutes
+    #[clap(long, value_name = "MINUTES")]
+    horizon: Option<u32>,
+
+    /// How to order the venues (alphabetical, priority or soonest)
+    #[clap(long, default_value_t = VenueOrder::default())]
+    order: VenueOrder,
+
+    /// Venue to list first when ordering by priority, may be given more than once (venues not
+    /// given are listed in the order of the venue registry)
+    #[clap(long = "priority", value_name = "VENUE")]
+    priorities: Vec<String>,
 }
 
-pub(crate) fn run(args: NowNextOptions, mut schedule: Schedule) {
-    let var_name = Mutators::default();
-    let mut mutations = var_name;
+pub(crate) fn run(args: NowNextOptions, mut schedule: Schedule, registry: &VenueRegistry) {
+    let mut mutations = Mutators::default();
+
+    // Filter by query if requested
+    args.query
+        .push_mutators(&mut mutations, args.rollover.rollover_hour);
 
     // Filter by venue if requested
     if let Some(venues) = args.venues.venues {
@@ -33,7 +71,23 @@ pub(crate) fn run(args: NowNextOptions, mut schedule: Schedule) {
 
     schedule.mutate(&mutations);
 
-    let now_next = schedule.now_and_next(args.now.now());
+    let mut now_next = schedule
+        .now_and_next_builder(args.now.now())
+        .rollover_hour(args.rollover.rollover_hour)
+        .order(args.order)
+        .priorities(args.priorities)
+        .venue_registry(registry);
+    if let Some(slots) = args.slots {
+        now_next = now_next.slots(slots);
+    }
+    if let Some(events) = args.events {
+        now_next = now_next.events(events);
+    }
+    if let Some(minutes) = args.horizon {
+        now_next = now_next.horizon(Duration::minutes(minutes.into()));
+    }
+    let now_next = now_next.build();
+    let show_later = args.slots.is_some() || args.horizon.is_some();
 
     println!("Now: {}", now_next.now);
 
@@ -43,22 +97,69 @@ pub(crate) fn run(args: NowNextOptions, mut schedule: Schedule) {
     table.column(0).set_header("Venue");
     table.column(1).set_header("Now");
     table.column(2).set_header("Next");
+    if show_later {
+        table.column(3).set_header("Later");
+    }
 
     let table_data: Vec<_> = now_next
         .guide
         .iter()
-        .map(|(venue, guide)| {
-            vec![
-                venue.to_string(),
-                format_event_now(&guide.now),
-                format_event_next(&guide.next),
-            ]
+        .map(|venue| {
+            let guide = &venue.now_and_next;
+            let mut row = vec![venue.venue.clone(), format_now(guide), format_next(guide)];
+            if show_later {
+                row.push(format_events_later(&guide.later));
+            }
+            row
         })
         .collect();
 
     table.println(table_data);
 }
 
+fn format_now(guide: &VenueNowAndNext) -> String {
+    match guide.remaining {
+        Some(remaining) => format!(
+            "{} ({} left)",
+            format_event_now(&guide.now),
+            format_duration(remaining)
+        ),
+        None => format_event_now(&guide.now),
+    }
+}
+
+fn format_next(guide: &VenueNowAndNext) -> String {
+    if guide.finished_for_day {
+        return "Finished for the day".to_string();
+    }
+
+    match (guide.starts_in, guide.gap) {
+        (Some(starts_in), Some(gap)) if !guide.now.is_empty() && gap > Duration::zero() => {
+            format!(
+                "{} (in {}, after a {} gap)",
+                format_event_next(&guide.next),
+                format_duration(starts_in),
+                format_duration(gap)
+            )
+        }
+        (Some(starts_in), _) => format!(
+            "{} (in {})",
+            format_event_next(&guide.next),
+            format_duration(starts_in)
+        ),
+        (None, _) => format_event_next(&guide.next),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
 fn format_event_now(events: &[Event]) -> String {
     match events.first() {
         Some(event) => format!(
@@ -82,3 +183,11 @@ fn format_event_next(events: &[Event]) -> String {
         None => "".to_string(),
     }
 }
+
+fn format_events_later(events: &[Event]) -> String {
+    events
+        .iter()
+        .map(|event| format_event_next(std::slice::from_ref(event)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,64 @@
+use clap::Parser;
+use emfcamp_schedule_api::schedule::{Schedule, search::SearchHit};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+#[derive(Debug, Parser)]
+pub(crate) struct SearchOptions {
+    #[arg(long, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Maximum number of results to show
+    #[arg(short = 'n', long, default_value = "10")]
+    limit: usize,
+
+    /// Words to search for in event titles, speakers and descriptions
+    #[arg(required = true)]
+    words: Vec<String>,
+}
+
+pub(crate) fn run(args: SearchOptions, schedule: Schedule) {
+    let mut stdout = StandardStream::stdout(args.color);
+
+    let index = schedule.search_index();
+    let hits = index.search(&args.words.join(" "));
+
+    if hits.is_empty() {
+        println!("No matching events");
+    }
+
+    for hit in hits.iter().take(args.limit) {
+        print_hit(&mut stdout, hit);
+    }
+}
+
+fn print_hit(stdout: &mut StandardStream, hit: &SearchHit) {
+    let event = hit.event;
+
+    stdout
+        .set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))
+        .unwrap();
+    println!("[{}] {}", event.id, event.title);
+
+    stdout
+        .set_color(ColorSpec::new().set_fg(Some(Color::Blue)))
+        .unwrap();
+    println!(
+        "{} @ {}, {}",
+        event.start.format("%a %H:%M"),
+        event.venue,
+        event.speaker
+    );
+
+    for (part, matched) in hit.snippet.parts() {
+        let mut color = ColorSpec::new();
+        if matched {
+            color.set_fg(Some(Color::Green)).set_bold(true);
+        }
+        stdout.set_color(&color).unwrap();
+        print!("{part}");
+    }
+
+    stdout.set_color(&ColorSpec::default()).unwrap();
+    println!();
+    println!();
+}
@@ -0,0 +1,36 @@
+use super::ListingTableCommonOptions;
+use clap::Parser;
+use emfcamp_schedule_api::schedule::Schedule;
+
+#[derive(Debug, Parser)]
+pub(crate) struct SpeakersOptions {
+    #[clap(flatten)]
+    table: ListingTableCommonOptions,
+
+    /// Show the events of a specific speaker, rather than listing all speakers
+    speaker: Option<String>,
+}
+
+pub(crate) fn run(args: SpeakersOptions, schedule: Schedule) {
+    match args.speaker {
+        Some(name) => match schedule.speaker(&name) {
+            Some(speaker) => {
+                match speaker.pronouns {
+                    Some(pronouns) => println!("{} ({pronouns})", speaker.name),
+                    None => println!("{}", speaker.name),
+                }
+
+                let mut events = speaker.events;
+                events.sort();
+
+                args.table.print(events);
+            }
+            None => println!("Failed to find speaker {name}"),
+        },
+        None => {
+            for speaker in schedule.speakers() {
+                println!("{} ({} events)", speaker.name, speaker.events.len());
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use super::{ListingTableCommonOptions, NowCommonOptions, VenueFilterCommonOptions};
-use crate::formatting::event_listing;
+use super::{
+    DayFilterCommonOptions, ListingTableCommonOptions, NowCommonOptions, QueryCommonOptions,
+    VenueFilterCommonOptions,
+};
 use clap::Parser;
 use emfcamp_schedule_api::schedule::{
     Schedule,
@@ -14,6 +16,12 @@ pub(crate) struct UpcomingOptions {
     #[clap(flatten)]
     venues: VenueFilterCommonOptions,
 
+    #[clap(flatten)]
+    day: DayFilterCommonOptions,
+
+    #[clap(flatten)]
+    query: QueryCommonOptions,
+
     #[clap(flatten)]
     now: NowCommonOptions,
 }
@@ -21,6 +29,14 @@ pub(crate) struct UpcomingOptions {
 pub(crate) fn run(args: UpcomingOptions, mut schedule: Schedule) {
     let mut mutations = Mutators::default();
 
+    // Filter by query if requested, before finished events are removed, as days named in the
+    // query are found from the events it is given
+    args.query
+        .push_mutators(&mut mutations, args.day.rollover.rollover_hour);
+
+    // Filter by festival day if requested
+    args.day.push_mutators(&mut mutations);
+
     // Filter by venue if requested
     if let Some(venues) = args.venues.venues {
         mutations.push(Box::new(mutation::AtVenues::new(venues)));
@@ -33,7 +49,5 @@ pub(crate) fn run(args: UpcomingOptions, mut schedule: Schedule) {
     mutations.push(Box::<SortedByStartTime>::default());
 
     schedule.mutate(&mutations);
-    let events = schedule.events;
-
-    event_listing::print_table(args.table.width.max_width, &args.table.columns, &events);
+    args.table.print(schedule.events);
 }
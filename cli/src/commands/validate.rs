@@ -0,0 +1,39 @@
+use anyhow::Result;
+use clap::Parser;
+use emfcamp_schedule_api::schedule::{Schedule, validate::Severity};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ValidateOptions {
+    #[arg(long, default_value = "auto")]
+    color: ColorChoice,
+}
+
+pub(crate) fn run(args: ValidateOptions, schedule: Schedule) -> Result<()> {
+    let mut stdout = StandardStream::stdout(args.color);
+    let report = schedule.validate();
+
+    for problem in &report.problems {
+        let (label, color) = match problem.severity() {
+            Severity::Error => ("error", Color::Red),
+            Severity::Warning => ("warning", Color::Yellow),
+        };
+
+        stdout
+            .set_color(ColorSpec::new().set_fg(Some(color)).set_bold(true))
+            .unwrap();
+        print!("{label}");
+        stdout.reset().unwrap();
+        println!(": {problem}");
+    }
+
+    let errors = report.errors().count();
+    let warnings = report.warnings().count();
+    println!("{errors} errors, {warnings} warnings");
+
+    if errors > 0 {
+        anyhow::bail!("Schedule has {errors} errors");
+    }
+
+    Ok(())
+}
@@ -1,7 +1,17 @@
-use emfcamp_schedule_api::schedule::Schedule;
+use emfcamp_schedule_api::schedule::{Schedule, venue::VenueRegistry};
 
-pub(crate) fn run(schedule: Schedule) {
-    for venue in schedule.venues() {
-        println!("{venue}");
+pub(crate) fn run(schedule: Schedule, registry: &VenueRegistry) {
+    if registry.venues.is_empty() {
+        for venue in schedule.venues() {
+            println!("{venue}");
+        }
+        return;
+    }
+
+    for area in schedule.areas(registry) {
+        println!("{}", area.name.as_deref().unwrap_or("Other"));
+        for venue in area.venues {
+            println!("  {venue}");
+        }
     }
 }
@@ -1,6 +1,6 @@
 use ascii_table::{AsciiTable, Width};
 use clap::ValueEnum;
-use emfcamp_schedule_api::schedule::event::{Event, Kind};
+use emfcamp_schedule_api::schedule::{event::Event, sessions::SessionGroup};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -23,13 +23,7 @@ impl Column {
         match self {
             Column::Id => event.id.to_string(),
             Column::Slug => event.slug.clone(),
-            Column::Type => match event.kind {
-                Kind::Talk => "Talk",
-                Kind::Workshop(_) => "Workshop",
-                Kind::YouthWorkshop => "Youth Workshop",
-                Kind::Performance => "Performance",
-            }
-            .to_string(),
+            Column::Type => event.kind.to_string(),
             Column::Start => event.start.format("%a %H:%M").to_string(),
             Column::StartVerbose => event.start.format("%Y-%m-%d %H:%M").to_string(),
             Column::End => event.end.format("%a %H:%M").to_string(),
@@ -73,13 +67,7 @@ pub(crate) fn default_columns() -> Vec<Column> {
 }
 
 pub(crate) fn print_table(max_width: usize, columns: &[Column], events: &[Event]) {
-    let mut table = AsciiTable::default();
-
-    table.set_max_width(Width::Fixed(max_width));
-
-    for (i, col) in columns.iter().enumerate() {
-        table.column(i).set_header(col.to_string());
-    }
+    let headers: Vec<_> = columns.iter().map(Column::to_string).collect();
 
     let table_data: Vec<_> = events
         .iter()
@@ -91,5 +79,44 @@ pub(crate) fn print_table(max_width: usize, columns: &[Column], events: &[Event]
         })
         .collect();
 
+    print(max_width, &headers, table_data);
+}
+
+/// Prints one row per group of sessions, showing the first session along with the start times of
+/// the others.
+pub(crate) fn print_grouped_table(max_width: usize, columns: &[Column], groups: &[SessionGroup]) {
+    let mut headers: Vec<_> = columns.iter().map(Column::to_string).collect();
+    headers.push("Also at".to_owned());
+
+    let table_data: Vec<_> = groups
+        .iter()
+        .map(|group| {
+            let mut row: Vec<_> = columns
+                .iter()
+                .map(|col| col.format_event_data(group.first()))
+                .collect();
+            row.push(
+                group.sessions[1..]
+                    .iter()
+                    .map(|event| Column::Start.format_event_data(event))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+            row
+        })
+        .collect();
+
+    print(max_width, &headers, table_data);
+}
+
+fn print(max_width: usize, headers: &[String], table_data: Vec<Vec<String>>) {
+    let mut table = AsciiTable::default();
+
+    table.set_max_width(Width::Fixed(max_width));
+
+    for (i, header) in headers.iter().enumerate() {
+        table.column(i).set_header(header);
+    }
+
     table.println(table_data);
 }
@@ -2,14 +2,22 @@ mod commands;
 mod formatting;
 
 use anyhow::Result;
+use chrono_tz::Tz;
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
+use emfcamp_schedule_api::schedule::{
+    Schedule,
+    decode::{DecodedSchedule, Decoding},
+    mutation::{CanonicalVenues, MutatorSpec, Pipeline},
+    venue::VenueRegistry,
+};
+use std::path::PathBuf;
 use url::Url;
 
 #[derive(Debug, Parser)]
 #[clap(version, about)]
 struct Cli {
-    /// URL of the schedule API to consume
+    /// URL of the schedule API to consume (a file:// URL may be used to read a saved snapshot)
     #[clap(
         long,
         env,
@@ -18,6 +26,29 @@ struct Cli {
     )]
     api_url: Url,
 
+    /// Read the schedule from a saved snapshot instead of the schedule API
+    #[clap(long, value_name = "PATH")]
+    from_file: Option<PathBuf>,
+
+    /// Skip events that cannot be decoded instead of failing
+    #[clap(long)]
+    lenient: bool,
+
+    /// Timezone in which the schedule API (or a file given by --from-file) gives times that have
+    /// no offset
+    #[clap(long, env, value_name = "TIMEZONE", default_value = "Europe/London")]
+    timezone: Tz,
+
+    /// Mutators to apply to the schedule before running the command, as a JSON file (or TOML,
+    /// with a .toml extension)
+    #[clap(long, env, value_name = "PATH")]
+    mutators: Option<PathBuf>,
+
+    /// Venue registry used to show each venue under a single name and to order and group venues
+    /// in listings, as a JSON file (or TOML, with a .toml extension)
+    #[clap(long, env, value_name = "PATH")]
+    venue_registry: Option<PathBuf>,
+
     #[clap(subcommand)]
     command: Command,
 }
@@ -36,9 +67,27 @@ enum Command {
     /// Show details for a specific event
     Details(commands::details::EventDetailsOptions),
 
-    /// List all venues
+    /// Search for events by title, speaker and description
+    Search(commands::search::SearchOptions),
+
+    /// List all venues, grouped into areas if a venue registry is given
     Venues,
 
+    /// List all speakers, or the events of one speaker
+    Speakers(commands::speakers::SpeakersOptions),
+
+    /// List the festival days and how many events start on each
+    Days(commands::days::DaysOptions),
+
+    /// Check the schedule for problems with its data (e.g. overlapping events)
+    Validate(commands::validate::ValidateOptions),
+
+    /// Save the schedule to a file, for later use with --from-file
+    Snapshot {
+        /// Path to save the schedule to
+        path: PathBuf,
+    },
+
     /// Generate shell completions
     ShellCompletions {
         /// The shell to generate completions for
@@ -50,16 +99,60 @@ enum Command {
 async fn main() -> Result<()> {
     let args = Cli::parse();
 
-    let client = emfcamp_schedule_api::Client::new(args.api_url);
+    let decoding = if args.lenient {
+        Decoding::Lenient
+    } else {
+        Decoding::Strict
+    };
+
+    let DecodedSchedule {
+        mut schedule,
+        rejected,
+    } = match args.from_file {
+        Some(path) => Schedule::from_file(path, decoding, args.timezone)?,
+        None => {
+            emfcamp_schedule_api::Client::builder(args.api_url)
+                .application(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+                .decoding(decoding)
+                .timezone(args.timezone)
+                .build()?
+                .get_decoded_schedule()
+                .await?
+        }
+    };
 
-    let schedule = client.get_schedule().await?;
+    for event in rejected {
+        eprintln!("Skipped event {}: {}", event.index, event.reason);
+    }
+
+    let venues = match args.venue_registry {
+        Some(path) => VenueRegistry::from_file(path)?,
+        None => VenueRegistry::default(),
+    };
+
+    let mut pipeline = match args.mutators {
+        Some(path) => Pipeline::from_file(path)?,
+        None => Pipeline::default(),
+    };
+    if !venues.venues.is_empty() {
+        let canonical_venues = CanonicalVenues::new(venues.clone());
+        pipeline
+            .mutators
+            .insert(0, MutatorSpec::CanonicalVenues(canonical_venues));
+    }
+    schedule.mutate(&pipeline.into());
 
     match args.command {
         Command::Full(args) => commands::full::run(args, schedule),
         Command::Upcoming(args) => commands::upcoming::run(args, schedule),
-        Command::NowNext(args) => commands::now_next::run(args, schedule),
+        Command::NowNext(args) => commands::now_next::run(args, schedule, &venues),
         Command::Details(args) => commands::details::run(args, schedule),
-        Command::Venues => commands::venues::run(schedule),
+        Command::Search(args) => commands::search::run(args, schedule),
+        Command::Venues => commands::venues::run(schedule, &venues),
+        Command::Speakers(args) => commands::speakers::run(args, schedule),
+        Command::Days(args) => commands::days::run(args, schedule),
+        Command::Validate(args) => commands::validate::run(args, schedule)?,
+        Command::Snapshot { path } => schedule.save(path)?,
         Command::ShellCompletions { shell } => print_shell_completions(shell),
     }
 
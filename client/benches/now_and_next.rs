@@ -0,0 +1,144 @@
+//! Compares answering time based queries by scanning every event with using a [`ScheduleIndex`].
+
+use chrono::{DateTime, Duration, FixedOffset};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use emfcamp_schedule_api::schedule::{
+    Schedule,
+    event::{Event, RelativeTime},
+};
+use serde_json::json;
+use std::hint::black_box;
+
+const DAYS: i64 = 4;
+const HOURS_PER_DAY: i64 = 14;
+
+fn epoch() -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339("2024-05-30T10:00:00+01:00").unwrap()
+}
+
+/// A festival sized schedule, with events of varying lengths back to back at each venue.
+fn schedule(venues: usize) -> Schedule {
+    let mut events = Vec::new();
+
+    for venue in 0..venues {
+        for day in 0..DAYS {
+            let mut start = epoch() + Duration::try_days(day).unwrap();
+            let end_of_day = start + Duration::try_hours(HOURS_PER_DAY).unwrap();
+
+            while start < end_of_day {
+                let minutes = [30, 45, 60, 90][(events.len() + venue) % 4];
+                let end = start + Duration::try_minutes(minutes).unwrap();
+
+                let event: Event = serde_json::from_value(json!({
+                    "id": events.len(),
+                    "slug": format!("event-{}", events.len()),
+                    "start_date": start.to_rfc3339(),
+                    "end_date": end.to_rfc3339(),
+                    "venue": format!("Venue {venue}"),
+                    "map_link": null,
+                    "title": format!("Event {}", events.len()),
+                    "speaker": "Someone",
+                    "pronouns": null,
+                    "description": "",
+                    "type": "talk",
+                    "may_record": null,
+                    "is_family_friendly": null,
+                    "link": "https://example.com",
+                }))
+                .unwrap();
+                events.push(event);
+
+                // Leave a changeover between events
+                start = end + Duration::try_minutes(10).unwrap();
+            }
+        }
+    }
+
+    Schedule { events }
+}
+
+/// A time every 15 minutes over the festival, as used when pre-rendering signage.
+fn times() -> Vec<DateTime<FixedOffset>> {
+    let step = Duration::try_minutes(15).unwrap();
+    let count = DAYS * 24 * 4;
+    (0..count as i32).map(|i| epoch() + step * i).collect()
+}
+
+fn on_at(c: &mut Criterion) {
+    let mut group = c.benchmark_group("on_at");
+    let times = times();
+
+    for venues in [10, 40] {
+        let schedule = schedule(venues);
+        let index = schedule.index();
+
+        group.bench_with_input(
+            BenchmarkId::new("scan", venues),
+            &schedule,
+            |b, schedule| {
+                b.iter(|| {
+                    for &t in &times {
+                        black_box(
+                            schedule
+                                .events
+                                .iter()
+                                .filter(|e| e.relative_to(t) == RelativeTime::Now)
+                                .count(),
+                        );
+                    }
+                })
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("index", venues), &index, |b, index| {
+            b.iter(|| {
+                for &t in &times {
+                    black_box(index.on_at(t).len());
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn now_and_next(c: &mut Criterion) {
+    let mut group = c.benchmark_group("now_and_next");
+    group.sample_size(10);
+    let times = times();
+
+    for venues in [10, 40] {
+        let schedule = schedule(venues);
+        let index = schedule.index();
+
+        group.bench_with_input(
+            BenchmarkId::new("scan", venues),
+            &schedule,
+            |b, schedule| {
+                b.iter(|| {
+                    for &t in &times {
+                        black_box(schedule.now_and_next_builder(t).slots(3).build());
+                    }
+                })
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("index", venues), &index, |b, index| {
+            b.iter(|| {
+                for &t in &times {
+                    black_box(index.now_and_next_builder(t).slots(3).build());
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn build_index(c: &mut Criterion) {
+    let schedule = schedule(40);
+    c.bench_function("build_index", |b| b.iter(|| black_box(schedule.index())));
+}
+
+criterion_group!(benches, on_at, now_and_next, build_index);
+criterion_main!(benches);
@@ -1,7 +1,8 @@
 /// This is intended as a long running test case for the announcer.
 ///
 /// It will use the 2022 schedule with a fake epoch via the adapter to simulate announcing the full
-/// 2022 schedule in real time.
+/// 2022 schedule, sped up by `TIME_SCALE` so that it does not take days to run (set it to 1.0 to
+/// run in real time).
 ///
 /// There is some automatic checking that things are happening at the correct times and sufficient
 /// console output to manually verify this.
@@ -24,6 +25,9 @@ use emfcamp_schedule_api::{
 use tracing::{error, info};
 use url::Url;
 
+/// How many times faster than real time to run the schedule.
+const TIME_SCALE: f64 = 72.0;
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
@@ -42,6 +46,7 @@ async fn main() -> Result<()> {
                     "fake_epoch",
                     &epoch.to_rfc3339_opts(SecondsFormat::Secs, false),
                 )
+                .append_pair("fake_time_scale", &TIME_SCALE.to_string())
                 .finish(),
         ));
 
@@ -4,23 +4,35 @@ mod utils;
 
 use crate::{
     Client,
-    schedule::{Schedule, event::Event},
+    schedule::{
+        Schedule,
+        decode::{DecodedSchedule, RejectedEvent},
+        event::Event,
+        mutation::Pipeline,
+        validate::Severity,
+    },
 };
 use chrono::{DateTime, Duration as ChronoDuration, FixedOffset, Utc};
 use derive_builder::Builder;
 use metrics::{counter, describe_counter, describe_gauge, gauge};
 use tokio::time::{Duration as TokioDuration, Interval};
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 const EVENT_METRIC_NAME: &str = "schedule_announcer_events";
 const SCHEDULE_UPDATE_METRIC_NAME: &str = "schedule_announcer_schedule_updates";
 const TIME_TO_NEXT_EVENT_METRIC_NAME: &str = "schedule_announcer_time_to_next_event";
+const VALIDATION_PROBLEMS_METRIC_NAME: &str = "schedule_announcer_validation_problems";
+const REJECTED_EVENTS_METRIC_NAME: &str = "schedule_announcer_rejected_events";
 
 #[derive(Debug, Builder)]
 #[builder(default)]
 pub struct AnnouncerSettings {
     schedule_refresh: TokioDuration,
     event_start_offset: ChronoDuration,
+
+    /// Mutators applied to the schedule each time it is retrieved, e.g. to announce only events
+    /// at certain venues.
+    pipeline: Pipeline,
 }
 
 impl Default for AnnouncerSettings {
@@ -28,6 +40,7 @@ impl Default for AnnouncerSettings {
         Self {
             schedule_refresh: TokioDuration::from_secs(60),
             event_start_offset: ChronoDuration::zero(),
+            pipeline: Pipeline::default(),
         }
     }
 }
@@ -71,6 +84,7 @@ pub struct Announcer {
     settings: AnnouncerSettings,
     client: Client,
     schedule: Schedule,
+    rejected: Vec<RejectedEvent>,
     schedule_update_interval: Interval,
     last_notified_event_marker: Option<LastNotifiedEventMarker>,
 }
@@ -90,8 +104,19 @@ impl Announcer {
             metrics::Unit::Seconds,
             "Time until the next event needs to be announced"
         );
+        describe_gauge!(
+            VALIDATION_PROBLEMS_METRIC_NAME,
+            "Number of problems found with the data in the current schedule"
+        );
+        describe_gauge!(
+            REJECTED_EVENTS_METRIC_NAME,
+            "Number of events skipped because they could not be decoded when the schedule was last retrieved"
+        );
 
-        let schedule = self::utils::get_sorted_schedule(&client).await?;
+        let DecodedSchedule { schedule, rejected } =
+            self::utils::get_sorted_schedule(&client, &settings.pipeline).await?;
+        report_problems(&schedule);
+        report_rejected(&rejected);
 
         let mut schedule_update_interval = tokio::time::interval(settings.schedule_refresh);
         schedule_update_interval.reset();
@@ -100,30 +125,47 @@ impl Announcer {
             settings,
             client,
             schedule,
+            rejected,
             schedule_update_interval,
             last_notified_event_marker: None,
         })
     }
 
     async fn update_schedule(&mut self) -> crate::Result<AnnouncerScheduleChanges> {
-        let schedule = self::utils::get_sorted_schedule(&self.client).await?;
+        let DecodedSchedule { schedule, rejected } =
+            self::utils::get_sorted_schedule(&self.client, &self.settings.pipeline).await?;
+        report_rejected(&rejected);
 
         let changes = if self.schedule == schedule {
             debug!("No changes in new schedule");
             counter!(SCHEDULE_UPDATE_METRIC_NAME, "result" => "ok", "changes" => "no").increment(1);
             AnnouncerScheduleChanges::NoChanges
         } else {
-            info!("New schedule is different from previously loaded");
+            let diff = self.schedule.diff(&schedule);
+            info!(
+                "New schedule is different from previously loaded ({} added, {} removed, {} changed)",
+                diff.added.len(),
+                diff.removed.len(),
+                diff.changed.len()
+            );
             counter!(SCHEDULE_UPDATE_METRIC_NAME, "result" => "ok", "changes" => "yes")
                 .increment(1);
+            report_problems(&schedule);
             AnnouncerScheduleChanges::Changes
         };
 
         self.schedule = schedule;
+        self.rejected = rejected;
 
         Ok(changes)
     }
 
+    /// Events that were skipped because they could not be decoded when the schedule was last
+    /// retrieved (only when decoding leniently).
+    pub fn rejected_events(&self) -> &[RejectedEvent] {
+        &self.rejected
+    }
+
     pub async fn poll(&mut self) -> crate::Result<AnnouncerPollResult> {
         loop {
             // Determine what the next event to announce is and in how much time it is due to be announced
@@ -183,6 +225,35 @@ impl Announcer {
     }
 }
 
+/// Logs any problems with the data in the schedule, so that they can be fixed before they cause
+/// confusion.
+fn report_problems(schedule: &Schedule) {
+    let report = schedule.validate();
+
+    for problem in &report.problems {
+        match problem.severity() {
+            Severity::Error => error!("Schedule problem: {problem}"),
+            Severity::Warning => warn!("Schedule problem: {problem}"),
+        }
+    }
+
+    gauge!(VALIDATION_PROBLEMS_METRIC_NAME, "severity" => "error")
+        .set(report.errors().count() as f64);
+    gauge!(VALIDATION_PROBLEMS_METRIC_NAME, "severity" => "warning")
+        .set(report.warnings().count() as f64);
+}
+
+/// Counts the events that had to be skipped to decode the schedule, so that they are noticed.
+fn report_rejected(rejected: &[RejectedEvent]) {
+    if !rejected.is_empty() {
+        warn!(
+            "{} events could not be decoded and were skipped",
+            rejected.len()
+        );
+    }
+    gauge!(REJECTED_EVENTS_METRIC_NAME).set(rejected.len() as f64);
+}
+
 fn get_next_event_to_announce(
     events: &[Event],
     event_offset: ChronoDuration,
@@ -1,19 +1,25 @@
 use crate::{
     Client,
     schedule::{
-        Schedule,
+        decode::DecodedSchedule,
         event::Event,
-        mutation::{Mutators, SortedByStartTime},
+        mutation::{Mutators, Pipeline, SortedByStartTime},
     },
 };
 use chrono::{DateTime, Duration as ChronoDuration, FixedOffset};
 use tokio::time::Duration as TokioDuration;
 use tracing::warn;
 
-pub(super) async fn get_sorted_schedule(client: &Client) -> crate::Result<Schedule> {
-    let mut schedule = client.get_schedule().await?;
-    schedule.mutate(&Mutators::new_single(Box::new(SortedByStartTime {})));
-    Ok(schedule)
+pub(super) async fn get_sorted_schedule(
+    client: &Client,
+    pipeline: &Pipeline,
+) -> crate::Result<DecodedSchedule> {
+    let mut decoded = client.get_decoded_schedule().await?;
+
+    let mut mutators = Mutators::from(pipeline.clone());
+    mutators.push(Box::new(SortedByStartTime {}));
+    decoded.schedule.mutate(&mutators);
+    Ok(decoded)
 }
 
 pub(super) fn get_duration_before_event_notification(
@@ -0,0 +1,131 @@
+use super::{Client, retry::Retry};
+use crate::schedule::{decode::Decoding, event::DEFAULT_TIMEZONE};
+use chrono_tz::Tz;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use std::time::Duration;
+use url::Url;
+
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub struct ClientBuilder {
+    url: Url,
+    http: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    retry: Retry,
+    user_agent: String,
+    headers: HeaderMap,
+    max_age: Duration,
+    decoding: Decoding,
+    timezone: Tz,
+}
+
+impl ClientBuilder {
+    pub(super) fn new(url: Url) -> Self {
+        Self {
+            url,
+            http: None,
+            timeout: None,
+            retry: Retry::default(),
+            user_agent: DEFAULT_USER_AGENT.to_owned(),
+            headers: HeaderMap::new(),
+            max_age: Duration::ZERO,
+            decoding: Decoding::default(),
+            timezone: DEFAULT_TIMEZONE,
+        }
+    }
+
+    /// Use an existing HTTP client rather than creating a new one.
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// Give up on a request to the API if it takes longer than this.
+    ///
+    /// By default there is no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry requests to the API that fail to connect or get a server error (5xx) response up to
+    /// this many times.
+    ///
+    /// By default requests are not retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retry.retries = retries;
+        self
+    }
+
+    /// The time to wait before the first retry, this is doubled for each subsequent retry (with a
+    /// bit of randomness added).
+    pub fn retry_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.retry.initial_backoff = initial;
+        self.retry.max_backoff = max;
+        self
+    }
+
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Identify the application making requests in the user agent, ahead of this library.
+    pub fn application(self, name: &str, version: &str) -> Self {
+        self.user_agent(format!("{name}/{version} {}", Self::default_user_agent()))
+    }
+
+    /// The user agent used if none is given, naming this library and its version.
+    pub fn default_user_agent() -> &'static str {
+        DEFAULT_USER_AGENT
+    }
+
+    /// Add a header to every request made to the API.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Reuse a previously retrieved schedule without contacting the API at all if it was
+    /// retrieved less than `max_age` ago.
+    ///
+    /// By default every call to [`Client::get_schedule`] makes a (conditional) request.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Choose how events that cannot be decoded are handled.
+    ///
+    /// By default a single bad event causes retrieving the schedule to fail.
+    pub fn decoding(mut self, decoding: Decoding) -> Self {
+        self.decoding = decoding;
+        self
+    }
+
+    /// The timezone used to interpret timestamps that have no offset.
+    ///
+    /// By default this is the timezone EMF takes place in, Europe/London.
+    pub fn timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    pub fn build(self) -> crate::Result<Client> {
+        let mut headers = self.headers;
+        headers.insert(USER_AGENT, HeaderValue::try_from(self.user_agent)?);
+
+        Ok(Client {
+            url: self.url,
+            http: self.http.unwrap_or_default(),
+            timeout: self.timeout,
+            retry: self.retry,
+            headers,
+            max_age: self.max_age,
+            decoding: self.decoding,
+            timezone: self.timezone,
+            cache: Default::default(),
+        })
+    }
+}
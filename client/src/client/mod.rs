@@ -0,0 +1,424 @@
+mod builder;
+mod retry;
+
+pub use self::builder::ClientBuilder;
+use self::retry::Retry;
+use crate::schedule::{
+    Schedule,
+    decode::{DecodedSchedule, Decoding, decode_schedule},
+};
+use chrono_tz::Tz;
+use reqwest::{
+    Response, StatusCode,
+    header::{ETAG, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+use url::Url;
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    url: Url,
+    http: reqwest::Client,
+    timeout: Option<Duration>,
+    retry: Retry,
+    headers: HeaderMap,
+    max_age: Duration,
+    decoding: Decoding,
+    timezone: Tz,
+    cache: Arc<Mutex<Option<CachedSchedule>>>,
+}
+
+/// The last schedule successfully retrieved from the API, along with the validators needed to make
+/// a conditional request for it.
+#[derive(Debug)]
+struct CachedSchedule {
+    schedule: DecodedSchedule,
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    fetched: Instant,
+}
+
+impl CachedSchedule {
+    fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        if let Some(etag) = &self.etag {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+
+        headers
+    }
+}
+
+impl Client {
+    /// Creates a client with the default settings.
+    pub fn new(url: Url) -> Self {
+        Self::builder(url)
+            .build()
+            .expect("default client settings should be valid")
+    }
+
+    pub fn builder(url: Url) -> ClientBuilder {
+        ClientBuilder::new(url)
+    }
+
+    pub async fn get_schedule(&self) -> crate::Result<Schedule> {
+        Ok(self.get_decoded_schedule().await?.schedule)
+    }
+
+    /// Retrieves the schedule, along with any events that were skipped when decoding it.
+    /// Events are only ever skipped when using [`Decoding::Lenient`].
+    pub async fn get_decoded_schedule(&self) -> crate::Result<DecodedSchedule> {
+        if self.url.scheme() == "file" {
+            return self.get_schedule_from_file();
+        }
+
+        let headers = {
+            let cache = self.cache.lock().unwrap();
+
+            match cache.as_ref() {
+                Some(cached) if cached.fetched.elapsed() < self.max_age => {
+                    debug!("Cached schedule is fresh, skipping request");
+                    return Ok(cached.schedule.clone());
+                }
+                Some(cached) => cached.conditional_headers(),
+                None => HeaderMap::new(),
+            }
+        };
+
+        let response = self.send_with_retries(headers).await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            let mut cache = self.cache.lock().unwrap();
+
+            if let Some(cached) = cache.as_mut() {
+                debug!("Schedule not modified, using cached schedule");
+                cached.fetched = Instant::now();
+                return Ok(cached.schedule.clone());
+            }
+        }
+
+        if !response.status().is_success() {
+            return Err(crate::Error::HttpStatus(response.status()));
+        }
+
+        let etag = response.headers().get(ETAG).cloned();
+        let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+
+        let data = response.bytes().await?;
+        let schedule = decode_schedule(&data, self.decoding, self.timezone)?;
+
+        *self.cache.lock().unwrap() = Some(CachedSchedule {
+            schedule: schedule.clone(),
+            etag,
+            last_modified,
+            fetched: Instant::now(),
+        });
+
+        Ok(schedule)
+    }
+
+    async fn send_with_retries(&self, headers: HeaderMap) -> crate::Result<Response> {
+        let mut retry = 0;
+
+        loop {
+            let result = self.send(headers.clone()).await;
+
+            let should_retry = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect(),
+            };
+
+            if !should_retry || retry >= self.retry.retries {
+                return match result {
+                    Ok(response) => Ok(response),
+                    Err(e) => Err(e.into()),
+                };
+            }
+
+            let backoff = self.retry.backoff(retry);
+            match &result {
+                Ok(response) => warn!(
+                    "Request failed with status {}, retrying in {backoff:?}",
+                    response.status()
+                ),
+                Err(e) => warn!("Request failed ({e}), retrying in {backoff:?}"),
+            }
+
+            tokio::time::sleep(backoff).await;
+            retry += 1;
+        }
+    }
+
+    async fn send(&self, headers: HeaderMap) -> reqwest::Result<Response> {
+        let mut request = self
+            .http
+            .get(self.url.clone())
+            .headers(self.headers.clone())
+            .headers(headers);
+
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+
+        request.send().await
+    }
+
+    fn get_schedule_from_file(&self) -> crate::Result<DecodedSchedule> {
+        let path = self
+            .url
+            .to_file_path()
+            .map_err(|_| crate::Error::NotAFileUrl(self.url.clone()))?;
+
+        debug!("Loading schedule from {}", path.display());
+        Schedule::from_file(path, self.decoding, self.timezone)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{schedule::event::Event, testing::DummyScheduleServer};
+    use chrono::{DateTime, Utc};
+    use reqwest::header::HeaderName;
+
+    #[tokio::test]
+    async fn unchanged_schedule_is_served_from_cache() {
+        let mut dummy_server = DummyScheduleServer::new(8101).await;
+
+        let now: DateTime<Utc> = Utc::now();
+        dummy_server.set_events(vec![Event::dummy(0, now.into())]);
+
+        let client = Client::new(dummy_server.url());
+
+        let first = client.get_schedule().await.unwrap();
+        let second = client.get_schedule().await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(dummy_server.request_count(), 2);
+        assert_eq!(dummy_server.not_modified_count(), 1);
+
+        dummy_server.set_events(vec![Event::dummy(1, now.into())]);
+
+        let third = client.get_schedule().await.unwrap();
+
+        assert_eq!(third.events, vec![dummy_server.event(0)]);
+        assert_eq!(dummy_server.request_count(), 3);
+        assert_eq!(dummy_server.not_modified_count(), 1);
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn fresh_schedule_skips_request() {
+        let mut dummy_server = DummyScheduleServer::new(8102).await;
+
+        let now: DateTime<Utc> = Utc::now();
+        dummy_server.set_events(vec![Event::dummy(0, now.into())]);
+
+        let client = Client::builder(dummy_server.url())
+            .max_age(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        let first = client.get_schedule().await.unwrap();
+        let second = client.get_schedule().await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(dummy_server.request_count(), 1);
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let mut dummy_server = DummyScheduleServer::new(8104).await;
+
+        let url = dummy_server.url().join("not-the-schedule").unwrap();
+        let client = Client::new(url);
+
+        let result = client.get_schedule().await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::HttpStatus(StatusCode::NOT_FOUND))
+        ));
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let mut dummy_server = DummyScheduleServer::new(8105).await;
+
+        let now: DateTime<Utc> = Utc::now();
+        dummy_server.set_events(vec![Event::dummy(0, now.into())]);
+        dummy_server.fail_next(2);
+
+        let client = Client::builder(dummy_server.url())
+            .retries(3)
+            .retry_backoff(Duration::from_millis(10), Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let schedule = client.get_schedule().await.unwrap();
+
+        assert_eq!(schedule.events, vec![dummy_server.event(0)]);
+        assert_eq!(dummy_server.request_count(), 3);
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_retries() {
+        let mut dummy_server = DummyScheduleServer::new(8106).await;
+
+        dummy_server.fail_next(5);
+
+        let client = Client::builder(dummy_server.url())
+            .retries(1)
+            .retry_backoff(Duration::from_millis(10), Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let result = client.get_schedule().await;
+
+        assert!(matches!(
+            result,
+            Err(crate::Error::HttpStatus(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+        assert_eq!(dummy_server.request_count(), 2);
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn retries_connection_errors() {
+        // Nothing is listening on this port
+        let url = Url::parse("http://localhost:8107/schedule").unwrap();
+
+        let client = Client::builder(url)
+            .retries(2)
+            .retry_backoff(Duration::from_millis(100), Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        let result = client.get_schedule().await;
+
+        assert!(matches!(result, Err(crate::Error::HttpError(e)) if e.is_connect()));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let mut dummy_server = DummyScheduleServer::new(8108).await;
+
+        dummy_server.set_delay(Duration::from_secs(5));
+
+        let client = Client::builder(dummy_server.url())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let result = client.get_schedule().await;
+
+        assert!(matches!(result, Err(crate::Error::HttpError(e)) if e.is_timeout()));
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn user_agent_and_headers() {
+        let mut dummy_server = DummyScheduleServer::new(8109).await;
+
+        let client = Client::builder(dummy_server.url())
+            .http_client(reqwest::Client::new())
+            .user_agent("test-agent/1.0")
+            .header(
+                HeaderName::from_static("x-test"),
+                HeaderValue::from_static("hello"),
+            )
+            .build()
+            .unwrap();
+
+        client.get_schedule().await.unwrap();
+
+        let headers = dummy_server.last_headers();
+        assert_eq!(headers["user-agent"], "test-agent/1.0");
+        assert_eq!(headers["x-test"], "hello");
+
+        dummy_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn application_user_agent() {
+        let mut dummy_server = DummyScheduleServer::new(8110).await;
+
+        let client = Client::builder(dummy_server.url())
+            .application("an-app", "1.2.3")
+            .build()
+            .unwrap();
+
+        client.get_schedule().await.unwrap();
+
+        assert_eq!(
+            dummy_server.last_headers()["user-agent"],
+            format!("an-app/1.2.3 {}", ClientBuilder::default_user_agent())
+        );
+
+        dummy_server.stop().await;
+    }
+
+    #[test]
+    fn invalid_user_agent() {
+        let url = Url::parse("http://localhost/schedule").unwrap();
+        let result = Client::builder(url).user_agent("bad\nagent").build();
+        assert!(matches!(result, Err(crate::Error::InvalidHeaderValue(_))));
+    }
+
+    #[tokio::test]
+    async fn schedule_from_file_url() {
+        let now: DateTime<Utc> = Utc::now();
+        let schedule = Schedule {
+            events: vec![Event::dummy(0, now.into())],
+        };
+
+        let path = std::env::temp_dir().join("emfcamp-schedule-api-client-test.json");
+        schedule.save(&path).unwrap();
+
+        let client = Client::new(Url::from_file_path(&path).unwrap());
+        assert_eq!(client.get_schedule().await.unwrap(), schedule);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stale_schedule_is_revalidated() {
+        let mut dummy_server = DummyScheduleServer::new(8103).await;
+
+        let now: DateTime<Utc> = Utc::now();
+        dummy_server.set_events(vec![Event::dummy(0, now.into())]);
+
+        let client = Client::builder(dummy_server.url())
+            .max_age(Duration::from_millis(500))
+            .build()
+            .unwrap();
+
+        client.get_schedule().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        client.get_schedule().await.unwrap();
+
+        assert_eq!(dummy_server.request_count(), 2);
+        assert_eq!(dummy_server.not_modified_count(), 1);
+
+        dummy_server.stop().await;
+    }
+}
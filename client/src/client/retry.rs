@@ -0,0 +1,59 @@
+use rand::Rng;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub(super) struct Retry {
+    pub(super) retries: u32,
+    pub(super) initial_backoff: Duration,
+    pub(super) max_backoff: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            retries: 0,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Retry {
+    /// Time to wait before making the given retry (starting from zero).
+    ///
+    /// The backoff is doubled for each retry, up to the maximum, then a random amount of up to half
+    /// of it is taken off so that many clients do not all retry at the same time.
+    pub(super) fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_backoff);
+
+        let jitter = rand::rng().random_range(0.0..=0.5);
+        backoff.mul_f64(1.0 - jitter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let retry = Retry {
+            retries: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+        };
+
+        for (n, expected) in [(0, 1), (1, 2), (2, 4), (3, 8), (4, 10), (9, 10)] {
+            let expected = Duration::from_secs(expected);
+            let backoff = retry.backoff(n);
+            assert!(backoff <= expected, "retry {n}: {backoff:?} > {expected:?}");
+            assert!(
+                backoff >= expected / 2,
+                "retry {n}: {backoff:?} < {expected:?}/2"
+            );
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+/// Loads configuration from a TOML file (if the file has a `.toml` extension) or a JSON file.
+pub(crate) fn load_config<T: DeserializeOwned>(path: &Path) -> crate::Result<T> {
+    let data = std::fs::read_to_string(path)?;
+
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        Ok(toml::from_str(&data)?)
+    } else {
+        Ok(serde_json::from_str(&data)?)
+    }
+}
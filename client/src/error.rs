@@ -2,6 +2,38 @@
 pub enum Error {
     #[error("HTTP error {0}")]
     HttpError(#[from] reqwest::Error),
+
+    #[error("Invalid HTTP header value {0}")]
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+
+    #[error("HTTP status {0}")]
+    HttpStatus(reqwest::StatusCode),
+
+    #[error("IO error {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("JSON error {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("TOML error {0}")]
+    TomlError(#[from] toml::de::Error),
+
+    #[error("Failed to decode event {index} at {path}: {source}")]
+    EventDecodeError {
+        index: usize,
+        path: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Failed to parse timestamp \"{value}\" of event {index} at {path}")]
+    TimestampParseError {
+        index: usize,
+        path: String,
+        value: String,
+    },
+
+    #[error("URL does not refer to a local file {0}")]
+    NotAFileUrl(url::Url),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
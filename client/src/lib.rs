@@ -1,10 +1,11 @@
 pub mod announcer;
 mod client;
+mod config;
 mod error;
 pub mod schedule;
 
 pub use crate::{
-    client::Client,
+    client::{Client, ClientBuilder},
     error::{Error, Result},
 };
 
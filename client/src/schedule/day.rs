@@ -0,0 +1,240 @@
+use super::{Schedule, event::Event};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeDelta, Weekday};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Events starting before this hour (local time) belong to the previous festival day.
+pub const DEFAULT_ROLLOVER_HOUR: u32 = 4;
+
+/// A day of the festival, which runs from the rollover hour on its date until the rollover hour
+/// on the following date, so that late night events belong to the evening they are part of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct FestivalDay(NaiveDate);
+
+impl FestivalDay {
+    /// The festival day a time falls on, using the local time of the timestamp.
+    /// Rollover hours past the end of the day are treated as 23.
+    pub fn of(time: DateTime<FixedOffset>, rollover_hour: u32) -> Self {
+        let rollover = TimeDelta::try_hours(rollover_hour.min(23).into()).unwrap();
+        let shifted = time.naive_local() - rollover;
+        Self(shifted.date())
+    }
+
+    /// The date the festival day starts on.
+    pub fn date(&self) -> NaiveDate {
+        self.0
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.0.weekday()
+    }
+}
+
+impl fmt::Display for FestivalDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format("%A %Y-%m-%d"))
+    }
+}
+
+/// Selects a festival day, either by day of the week (e.g. `saturday` or `sat`) or by date
+/// (e.g. `2024-06-01`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DaySelector {
+    Weekday(Weekday),
+    Date(NaiveDate),
+}
+
+impl DaySelector {
+    pub fn matches(&self, day: FestivalDay) -> bool {
+        match self {
+            DaySelector::Weekday(weekday) => day.weekday() == *weekday,
+            DaySelector::Date(date) => day.date() == *date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid day `{0}`, expected a day of the week or a date")]
+pub struct InvalidDay(String);
+
+impl FromStr for DaySelector {
+    type Err = InvalidDay;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(weekday) = parse_weekday(s) {
+            Ok(Self::Weekday(weekday))
+        } else if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            Ok(Self::Date(date))
+        } else {
+            Err(InvalidDay(s.to_owned()))
+        }
+    }
+}
+
+impl TryFrom<String> for DaySelector {
+    type Error = InvalidDay;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<DaySelector> for String {
+    fn from(day: DaySelector) -> Self {
+        day.to_string()
+    }
+}
+
+impl fmt::Display for DaySelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DaySelector::Weekday(weekday) => write!(f, "{}", weekday.to_string().to_lowercase()),
+            DaySelector::Date(date) => write!(f, "{date}"),
+        }
+    }
+}
+
+/// Parses a day of the week, either in full or abbreviated, ignoring case.
+pub(crate) fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s.to_lowercase().as_str() {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl Schedule {
+    /// The festival days on which events start, in order.
+    pub fn days(&self) -> Vec<FestivalDay> {
+        self.days_with_rollover(DEFAULT_ROLLOVER_HOUR)
+    }
+
+    pub fn days_with_rollover(&self, rollover_hour: u32) -> Vec<FestivalDay> {
+        let mut days: Vec<_> = self
+            .events
+            .iter()
+            .map(|e| FestivalDay::of(e.start, rollover_hour))
+            .collect();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    /// The events grouped by the festival day they start on, in order of day.
+    /// The order of events within each day is kept.
+    pub fn events_by_day(&self, rollover_hour: u32) -> Vec<(FestivalDay, Vec<Event>)> {
+        self.days_with_rollover(rollover_hour)
+            .into_iter()
+            .map(|day| {
+                let events = self
+                    .events
+                    .iter()
+                    .filter(|e| FestivalDay::of(e.start, rollover_hour) == day)
+                    .cloned()
+                    .collect();
+                (day, events)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::time;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn schedule() -> Schedule {
+        Schedule {
+            events: vec![
+                Event::dummy(0, time("2024-05-31T10:00:00+01:00")),
+                Event::dummy(1, time("2024-05-31T23:30:00+01:00")),
+                Event::dummy(2, time("2024-06-01T01:30:00+01:00")),
+                Event::dummy(3, time("2024-06-01T10:00:00+01:00")),
+            ],
+        }
+    }
+
+    #[test]
+    fn festival_day() {
+        let day = FestivalDay::of(time("2024-06-01T03:59:00+01:00"), 4);
+        assert_eq!(day.date(), date("2024-05-31"));
+        assert_eq!(day.weekday(), Weekday::Fri);
+        assert_eq!(day.to_string(), "Friday 2024-05-31");
+
+        let day = FestivalDay::of(time("2024-06-01T04:00:00+01:00"), 4);
+        assert_eq!(day.date(), date("2024-06-01"));
+
+        let day = FestivalDay::of(time("2024-06-01T23:30:00+01:00"), u32::MAX);
+        assert_eq!(day.date(), date("2024-06-01"));
+
+        // Uses the local time of the timestamp, not UTC
+        let day = FestivalDay::of(time("2024-06-01T00:30:00+01:00"), 0);
+        assert_eq!(day.date(), date("2024-06-01"));
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(
+            schedule().days(),
+            vec![
+                FestivalDay(date("2024-05-31")),
+                FestivalDay(date("2024-06-01"))
+            ]
+        );
+
+        assert_eq!(
+            schedule().days_with_rollover(0),
+            vec![
+                FestivalDay(date("2024-05-31")),
+                FestivalDay(date("2024-06-01"))
+            ]
+        );
+    }
+
+    #[test]
+    fn events_by_day() {
+        let ids: Vec<Vec<u32>> = schedule()
+            .events_by_day(DEFAULT_ROLLOVER_HOUR)
+            .into_iter()
+            .map(|(_, events)| events.iter().map(|e| e.id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![0, 1, 2], vec![3]]);
+
+        let ids: Vec<Vec<u32>> = schedule()
+            .events_by_day(0)
+            .into_iter()
+            .map(|(_, events)| events.iter().map(|e| e.id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn day_selector() {
+        let friday = FestivalDay(date("2024-05-31"));
+
+        let selector: DaySelector = "Friday".parse().unwrap();
+        assert_eq!(selector, DaySelector::Weekday(Weekday::Fri));
+        assert!(selector.matches(friday));
+        assert_eq!(selector.to_string(), "fri");
+
+        let selector: DaySelector = "2024-05-31".parse().unwrap();
+        assert!(selector.matches(friday));
+        assert!(!selector.matches(FestivalDay(date("2024-06-01"))));
+
+        assert_eq!(
+            "someday".parse::<DaySelector>().unwrap_err().to_string(),
+            "invalid day `someday`, expected a day of the week or a date"
+        );
+    }
+}
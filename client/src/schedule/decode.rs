@@ -0,0 +1,228 @@
+use super::{
+    Schedule,
+    event::{Event, timestamp},
+};
+use crate::Error;
+use chrono::SecondsFormat;
+use chrono_tz::Tz;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+
+/// Fields of an event that are parsed by [`timestamp::parse_timestamp_string`].
+const TIMESTAMP_FIELDS: [&str; 2] = ["start_date", "end_date"];
+
+/// How to handle events that cannot be decoded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Decoding {
+    /// Fail to decode the schedule if any single event cannot be decoded.
+    #[default]
+    Strict,
+
+    /// Skip events that cannot be decoded, reporting them alongside the schedule.
+    Lenient,
+}
+
+/// An event that was skipped when decoding a schedule leniently.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RejectedEvent {
+    /// Position of the event in the upstream data.
+    pub index: usize,
+
+    /// The event as it was received.
+    pub data: Value,
+
+    /// Why the event could not be decoded.
+    pub reason: String,
+}
+
+/// A schedule, along with any events that had to be skipped to decode it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSchedule {
+    pub schedule: Schedule,
+    pub rejected: Vec<RejectedEvent>,
+}
+
+/// Decodes a JSON array of events, reporting which event and field caused a failure.
+/// Timestamps without an offset are taken to be local time in `timezone`.
+pub(crate) fn decode_schedule(
+    data: &[u8],
+    decoding: Decoding,
+    timezone: Tz,
+) -> crate::Result<DecodedSchedule> {
+    let values: Vec<Value> = serde_json::from_slice(data)?;
+
+    let mut events = Vec::with_capacity(values.len());
+    let mut rejected = Vec::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        match event_from_value(index, &value, timezone) {
+            Ok(event) => events.push(event),
+            Err(err) => match decoding {
+                Decoding::Strict => return Err(err),
+                Decoding::Lenient => {
+                    warn!("Skipping event: {err}");
+                    rejected.push(RejectedEvent {
+                        index,
+                        data: value,
+                        reason: err.to_string(),
+                    });
+                }
+            },
+        }
+    }
+
+    Ok(DecodedSchedule {
+        schedule: Schedule { events },
+        rejected,
+    })
+}
+
+fn event_from_value(index: usize, value: &Value, timezone: Tz) -> crate::Result<Event> {
+    let mut value = value.clone();
+
+    // Resolve timestamps here, where the timezone is known, leaving RFC 3339 timestamps for the
+    // event deserializer to parse
+    for field in TIMESTAMP_FIELDS {
+        if let Some(Value::String(s)) = value.get_mut(field) {
+            let t = timestamp::parse_timestamp_string(s, timezone).map_err(|_| {
+                Error::TimestampParseError {
+                    index,
+                    path: field.to_owned(),
+                    value: s.clone(),
+                }
+            })?;
+            *s = t.to_rfc3339_opts(SecondsFormat::AutoSi, false);
+        }
+    }
+
+    serde_path_to_error::deserialize(&value).map_err(|err| Error::EventDecodeError {
+        index,
+        path: err.path().to_string(),
+        source: err.into_inner(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::{Kind, timestamp::DEFAULT_TIMEZONE};
+    use chrono::DateTime;
+
+    const GOOD_EVENT: &str = r#"{
+        "id": 1,
+        "slug": "good",
+        "start_date": "2024-05-31 10:00:00",
+        "end_date": "2024-05-31 10:30:00",
+        "venue": "Stage A",
+        "map_link": "",
+        "title": "Good",
+        "speaker": "",
+        "pronouns": "",
+        "description": "",
+        "type": "talk",
+        "may_record": true,
+        "is_family_friendly": false,
+        "link": "https://www.emfcamp.org/schedule/2024/1-good"
+    }"#;
+
+    fn events_json(events: &[String]) -> Vec<u8> {
+        format!("[{}]", events.join(",")).into_bytes()
+    }
+
+    #[test]
+    fn good() {
+        let data = events_json(&[GOOD_EVENT.to_owned(), GOOD_EVENT.to_owned()]);
+        let decoded = decode_schedule(&data, Decoding::Strict, DEFAULT_TIMEZONE).unwrap();
+        assert_eq!(decoded.schedule.events.len(), 2);
+        assert!(decoded.rejected.is_empty());
+    }
+
+    #[test]
+    fn not_an_array() {
+        let result = decode_schedule(GOOD_EVENT.as_bytes(), Decoding::Lenient, DEFAULT_TIMEZONE);
+        assert!(matches!(result, Err(Error::JsonError(_))));
+    }
+
+    #[test]
+    fn bad_timestamp() {
+        let bad = GOOD_EVENT.replace("2024-05-31 10:30:00", "half ten");
+        let data = events_json(&[GOOD_EVENT.to_owned(), bad]);
+
+        match decode_schedule(&data, Decoding::Strict, DEFAULT_TIMEZONE) {
+            Err(Error::TimestampParseError { index, path, value }) => {
+                assert_eq!(index, 1);
+                assert_eq!(path, "end_date");
+                assert_eq!(value, "half ten");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bad_field() {
+        let bad = GOOD_EVENT.replace(r#""id": 1"#, r#""id": "one""#);
+        let data = events_json(&[bad, GOOD_EVENT.to_owned()]);
+
+        match decode_schedule(&data, Decoding::Strict, DEFAULT_TIMEZONE) {
+            Err(Error::EventDecodeError { index, path, .. }) => {
+                assert_eq!(index, 0);
+                assert_eq!(path, "id");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_kind() {
+        let event = GOOD_EVENT.replace(r#""type": "talk""#, r#""type": "lightning", "slots": 6"#);
+        let data = events_json(&[event]);
+
+        let decoded = decode_schedule(&data, Decoding::Strict, DEFAULT_TIMEZONE).unwrap();
+
+        let event = &decoded.schedule.events[0];
+        assert_eq!(
+            event.kind,
+            Kind::Other {
+                type_name: "lightning".to_owned(),
+            }
+        );
+        assert_eq!(event.extra["slots"], 6);
+    }
+
+    #[test]
+    fn timezone() {
+        let data = events_json(&[GOOD_EVENT.to_owned()]);
+        let decoded =
+            decode_schedule(&data, Decoding::Strict, chrono_tz::Europe::Amsterdam).unwrap();
+
+        assert_eq!(
+            decoded.schedule.events[0].start,
+            DateTime::parse_from_rfc3339("2024-05-31T10:00:00+02:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn lenient_skips_bad_events() {
+        let bad_timestamp = GOOD_EVENT.replace("2024-05-31 10:30:00", "half ten");
+        let bad_type = GOOD_EVENT
+            .replace(r#""id": 1"#, r#""id": 3"#)
+            .replace(r#""type": "talk""#, r#""type": 7"#);
+        let data = events_json(&[
+            bad_timestamp,
+            GOOD_EVENT.to_owned(),
+            bad_type,
+            GOOD_EVENT.to_owned(),
+        ]);
+
+        let decoded = decode_schedule(&data, Decoding::Lenient, DEFAULT_TIMEZONE).unwrap();
+
+        assert_eq!(decoded.schedule.events.len(), 2);
+
+        assert_eq!(decoded.rejected.len(), 2);
+        assert_eq!(decoded.rejected[0].index, 0);
+        assert!(decoded.rejected[0].reason.contains("half ten"));
+        assert_eq!(decoded.rejected[1].index, 2);
+        assert_eq!(decoded.rejected[1].data["id"], 3);
+    }
+}
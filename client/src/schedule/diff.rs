@@ -0,0 +1,228 @@
+use super::{Schedule, event::Event};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The differences between two versions of a schedule.
+/// Events are matched between the two schedules by their ID.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ScheduleDiff {
+    /// Events that are only in the new schedule.
+    pub added: Vec<Event>,
+
+    /// Events that are only in the old schedule.
+    pub removed: Vec<Event>,
+
+    /// Events that are in both schedules, but differ in some way.
+    pub changed: Vec<ChangedEvent>,
+}
+
+impl ScheduleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ChangedEvent {
+    pub old: Event,
+    pub new: Event,
+
+    pub start: Option<FieldChange<DateTime<FixedOffset>>>,
+    pub end: Option<FieldChange<DateTime<FixedOffset>>>,
+    pub venue: Option<FieldChange<String>>,
+    pub title: Option<FieldChange<String>>,
+}
+
+impl ChangedEvent {
+    fn new(old: &Event, new: &Event) -> Self {
+        Self {
+            old: old.clone(),
+            new: new.clone(),
+            start: FieldChange::compare(&old.start, &new.start),
+            end: FieldChange::compare(&old.end, &new.end),
+            venue: FieldChange::compare(&old.venue, &new.venue),
+            title: FieldChange::compare(&old.title, &new.title),
+        }
+    }
+
+    /// True if the event has moved in time or space, as opposed to having only cosmetic changes.
+    pub fn is_rescheduled(&self) -> bool {
+        self.start.is_some() || self.end.is_some() || self.venue.is_some()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FieldChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: Clone + PartialEq> FieldChange<T> {
+    fn compare(old: &T, new: &T) -> Option<Self> {
+        if old == new {
+            None
+        } else {
+            Some(Self {
+                old: old.clone(),
+                new: new.clone(),
+            })
+        }
+    }
+}
+
+impl Schedule {
+    /// Compare this schedule (treated as the old version) with another (treated as the new version).
+    pub fn diff(&self, other: &Schedule) -> ScheduleDiff {
+        let old: HashMap<u32, &Event> = self.events.iter().map(|e| (e.id, e)).collect();
+        let new: HashMap<u32, &Event> = other.events.iter().map(|e| (e.id, e)).collect();
+
+        let mut diff = ScheduleDiff::default();
+
+        for event in &other.events {
+            match old.get(&event.id) {
+                Some(old_event) if *old_event != event => {
+                    diff.changed.push(ChangedEvent::new(old_event, event));
+                }
+                Some(_) => {}
+                None => diff.added.push(event.clone()),
+            }
+        }
+
+        diff.removed = self
+            .events
+            .iter()
+            .filter(|e| !new.contains_key(&e.id))
+            .cloned()
+            .collect();
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::time;
+    use chrono::Duration;
+
+    #[test]
+    fn no_changes() {
+        let schedule = Schedule {
+            events: vec![
+                Event::dummy(0, time("2024-03-12T20:00:00+00:00")),
+                Event::dummy(1, time("2024-03-12T21:00:00+00:00")),
+            ],
+        };
+
+        let diff = schedule.diff(&schedule.clone());
+
+        assert!(diff.is_empty());
+        assert_eq!(diff, ScheduleDiff::default());
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let old = Schedule {
+            events: vec![
+                Event::dummy(0, time("2024-03-12T20:00:00+00:00")),
+                Event::dummy(1, time("2024-03-12T21:00:00+00:00")),
+            ],
+        };
+
+        let new = Schedule {
+            events: vec![
+                Event::dummy(1, time("2024-03-12T21:00:00+00:00")),
+                Event::dummy(2, time("2024-03-12T22:00:00+00:00")),
+            ],
+        };
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added, vec![new.events[1].clone()]);
+        assert_eq!(diff.removed, vec![old.events[0].clone()]);
+        assert_eq!(diff.changed, Vec::default());
+    }
+
+    #[test]
+    fn rescheduled() {
+        let old = Schedule {
+            events: vec![Event::dummy(0, time("2024-03-12T20:00:00+00:00"))],
+        };
+
+        let new = Schedule {
+            events: vec![{
+                let mut e = old.events[0].clone();
+                e.start += Duration::try_minutes(30).unwrap();
+                e.end += Duration::try_minutes(30).unwrap();
+                e.venue = "venue 2".to_owned();
+                e
+            }],
+        };
+
+        let diff = old.diff(&new);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+
+        let changed = &diff.changed[0];
+        assert!(changed.is_rescheduled());
+        assert_eq!(
+            changed.start,
+            Some(FieldChange {
+                old: time("2024-03-12T20:00:00+00:00"),
+                new: time("2024-03-12T20:30:00+00:00"),
+            })
+        );
+        assert_eq!(
+            changed.end,
+            Some(FieldChange {
+                old: time("2024-03-12T21:00:00+00:00"),
+                new: time("2024-03-12T21:30:00+00:00"),
+            })
+        );
+        assert_eq!(
+            changed.venue,
+            Some(FieldChange {
+                old: "".to_owned(),
+                new: "venue 2".to_owned(),
+            })
+        );
+        assert_eq!(changed.title, None);
+    }
+
+    #[test]
+    fn cosmetic_change() {
+        let old = Schedule {
+            events: vec![Event::dummy(0, time("2024-03-12T20:00:00+00:00"))],
+        };
+
+        let new = Schedule {
+            events: vec![{
+                let mut e = old.events[0].clone();
+                e.title = "A talk about LoRa".to_owned();
+                e.description = "Some words".to_owned();
+                e
+            }],
+        };
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.changed.len(), 1);
+
+        let changed = &diff.changed[0];
+        assert!(!changed.is_rescheduled());
+        assert_eq!(changed.start, None);
+        assert_eq!(changed.end, None);
+        assert_eq!(changed.venue, None);
+        assert_eq!(
+            changed.title,
+            Some(FieldChange {
+                old: "".to_owned(),
+                new: "A talk about LoRa".to_owned(),
+            })
+        );
+        assert_eq!(changed.new.description, "Some words");
+    }
+}
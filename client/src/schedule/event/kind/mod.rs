@@ -1,22 +1,77 @@
 mod workshop;
 
 pub use self::workshop::Workshop;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _, ser::Error as _};
+use serde_json::{Map, Value};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(tag = "type", rename_all = "lowercase")]
+const TYPE_FIELD: &str = "type";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Kind {
-    // TODO
+    /// The upstream API has no fields specific to talks.
     Talk,
 
     Workshop(Workshop),
 
-    // TODO
-    YouthWorkshop,
+    /// Youth workshops have the same details as workshops.
+    YouthWorkshop(Workshop),
 
-    // TODO
+    /// The upstream API has no fields specific to performances.
     Performance,
+
+    /// A kind of event that is not (yet) known about.
+    ///
+    /// As it is not possible to tell which fields are specific to an unknown kind, any fields of
+    /// the event that are not otherwise known about are kept in [`super::Event::extra`].
+    Other {
+        type_name: String,
+    },
+}
+
+impl Kind {
+    /// The value of the `type` field used by the upstream API.
+    pub fn type_name(&self) -> &str {
+        match &self {
+            Kind::Talk => "talk",
+            Kind::Workshop(_) => "workshop",
+            Kind::YouthWorkshop(_) => "youthworkshop",
+            Kind::Performance => "performance",
+            Kind::Other { type_name } => type_name,
+        }
+    }
+
+    /// The workshop details, for both workshops and youth workshops.
+    pub fn workshop(&self) -> Option<&Workshop> {
+        match &self {
+            Kind::Workshop(workshop) | Kind::YouthWorkshop(workshop) => Some(workshop),
+            _ => None,
+        }
+    }
+}
+
+/// Deserializes the fields of an event that do not belong to either the event itself or its kind.
+///
+/// Both this and [`Kind`] are given every field that [`super::Event`] does not know about, so the fields
+/// that the kind takes ownership of are removed here.
+pub(super) fn deserialize_extra_fields<'de, D>(
+    deserializer: D,
+) -> Result<Map<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut fields = Map::deserialize(deserializer)?;
+
+    match fields.remove(TYPE_FIELD).as_ref().and_then(Value::as_str) {
+        Some("workshop") | Some("youthworkshop") => {
+            for name in workshop::FIELDS {
+                fields.remove(name);
+            }
+        }
+        _ => {}
+    }
+
+    Ok(fields)
 }
 
 impl Display for Kind {
@@ -24,8 +79,136 @@ impl Display for Kind {
         match &self {
             Kind::Talk => write!(f, "Talk"),
             Kind::Workshop(_) => write!(f, "Workshop"),
-            Kind::YouthWorkshop => write!(f, "Youth Workshop"),
+            Kind::YouthWorkshop(_) => write!(f, "Youth Workshop"),
             Kind::Performance => write!(f, "Performance"),
+            Kind::Other { type_name } => write!(f, "{type_name}"),
         }
     }
 }
+
+impl<'de> Deserialize<'de> for Kind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = Map::deserialize(deserializer)?;
+
+        let type_name = match fields.remove(TYPE_FIELD) {
+            Some(Value::String(s)) => s,
+            Some(other) => {
+                return Err(D::Error::custom(format!(
+                    "invalid event type {other}, expected a string"
+                )));
+            }
+            None => return Err(D::Error::missing_field(TYPE_FIELD)),
+        };
+
+        let workshop =
+            |fields| Workshop::deserialize(Value::Object(fields)).map_err(D::Error::custom);
+
+        match type_name.as_str() {
+            "talk" => Ok(Kind::Talk),
+            "workshop" => Ok(Kind::Workshop(workshop(fields)?)),
+            "youthworkshop" => Ok(Kind::YouthWorkshop(workshop(fields)?)),
+            "performance" => Ok(Kind::Performance),
+            _ => Ok(Kind::Other { type_name }),
+        }
+    }
+}
+
+impl Serialize for Kind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut fields = match &self {
+            Kind::Talk | Kind::Performance | Kind::Other { .. } => Map::new(),
+            Kind::Workshop(workshop) | Kind::YouthWorkshop(workshop) => {
+                match serde_json::to_value(workshop).map_err(S::Error::custom)? {
+                    Value::Object(fields) => fields,
+                    _ => unreachable!("workshop should always serialize as an object"),
+                }
+            }
+        };
+
+        fields.insert(
+            TYPE_FIELD.to_owned(),
+            Value::String(self.type_name().to_owned()),
+        );
+
+        fields.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn talk() {
+        let kind: Kind = serde_json::from_value(json!({ "type": "talk" })).unwrap();
+        assert_eq!(kind, Kind::Talk);
+        assert_eq!(
+            serde_json::to_value(&kind).unwrap(),
+            json!({ "type": "talk" })
+        );
+    }
+
+    #[test]
+    fn youth_workshop() {
+        let data = json!({
+            "type": "youthworkshop",
+            "cost": "Free",
+            "equiptment": "Scissors",
+            "age_range": "8+",
+            "attendees": "10",
+        });
+
+        let kind: Kind = serde_json::from_value(data.clone()).unwrap();
+        assert_eq!(
+            kind,
+            Kind::YouthWorkshop(Workshop {
+                cost: "Free".to_owned(),
+                equiptment: Some("Scissors".to_owned()),
+                age_range: "8+".to_owned(),
+                attendees: Some("10".to_owned()),
+            })
+        );
+        assert_eq!(kind.to_string(), "Youth Workshop");
+        assert_eq!(serde_json::to_value(&kind).unwrap(), data);
+    }
+
+    #[test]
+    fn youth_workshop_missing_fields() {
+        let result = serde_json::from_value::<Kind>(json!({ "type": "youthworkshop" }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn other() {
+        let data = json!({
+            "type": "lightning",
+            "slots": 6,
+        });
+
+        let kind: Kind = serde_json::from_value(data).unwrap();
+        assert_eq!(
+            kind,
+            Kind::Other {
+                type_name: "lightning".to_owned(),
+            }
+        );
+        assert_eq!(kind.to_string(), "lightning");
+        assert_eq!(
+            serde_json::to_value(&kind).unwrap(),
+            json!({ "type": "lightning" })
+        );
+    }
+
+    #[test]
+    fn missing_type() {
+        let result = serde_json::from_value::<Kind>(json!({ "cost": "Free" }));
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// Names of the fields of [`Workshop`] as used by the upstream API.
+pub(super) const FIELDS: [&str; 4] = ["cost", "equiptment", "age_range", "attendees"];
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Workshop {
     pub cost: String,
@@ -10,3 +13,147 @@ pub struct Workshop {
 
     pub attendees: Option<String>,
 }
+
+const CURRENCY_SYMBOLS: &[char] = &['£', '$', '€'];
+
+impl Workshop {
+    /// Whether the workshop costs nothing to attend, if that can be told from the free text cost.
+    pub fn is_free(&self) -> Option<bool> {
+        let cost = self.cost.trim().to_lowercase();
+
+        if cost.starts_with("free") || ["none", "nothing"].contains(&cost.as_str()) {
+            Some(true)
+        } else if let Some(amount) = leading_amount(&cost) {
+            Some(amount == 0.0)
+        } else if cost.contains(|c: char| c.is_ascii_digit()) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether someone of the given age can attend, if that can be told from the free text age
+    /// range (e.g. "8+", "12-16", "All ages", "Adults only").
+    pub fn suitable_for_age(&self, age: u32) -> Option<bool> {
+        let (min, max) = parse_age_range(&self.age_range)?;
+        Some(min.is_none_or(|min| age >= min) && max.is_none_or(|max| age <= max))
+    }
+}
+
+/// The amount at the start of a cost, after an optional currency symbol (e.g. 5 for "£5 for
+/// materials" or 0 for "0 - donations welcome").
+fn leading_amount(cost: &str) -> Option<f64> {
+    let cost = cost.trim_start();
+    let cost = cost
+        .strip_prefix(CURRENCY_SYMBOLS)
+        .unwrap_or(cost)
+        .trim_start();
+
+    let len = cost
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(cost.len());
+    cost[..len].trim_end_matches('.').parse().ok()
+}
+
+/// Parses an age range into its (inclusive) lower and upper bounds.
+fn parse_age_range(s: &str) -> Option<(Option<u32>, Option<u32>)> {
+    let s = s.trim().to_lowercase();
+    let number = |s: &str| {
+        s.trim()
+            .trim_end_matches(['s', '+'])
+            .trim()
+            .parse::<u32>()
+            .ok()
+    };
+
+    if s.starts_with("all") || s == "any" {
+        Some((None, None))
+    } else if s.starts_with("adult") {
+        Some((Some(18), None))
+    } else if let Some(max) = s.strip_prefix("under") {
+        Some((None, Some(number(max)?.checked_sub(1)?)))
+    } else if let Some((min, max)) = s.split_once(['-', '–']) {
+        Some((Some(number(min)?), Some(number(max)?)))
+    } else {
+        Some((Some(number(&s)?), None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn workshop(cost: &str, age_range: &str) -> Workshop {
+        Workshop {
+            cost: cost.to_owned(),
+            equiptment: None,
+            age_range: age_range.to_owned(),
+            attendees: None,
+        }
+    }
+
+    #[test]
+    fn is_free() {
+        for (cost, expected) in [
+            ("Free", Some(true)),
+            ("free (donations welcome)", Some(true)),
+            ("£0", Some(true)),
+            ("0.00", Some(true)),
+            ("£ 0", Some(true)),
+            ("£0.00", Some(true)),
+            ("£5.00", Some(false)),
+            ("£0 (materials provided)", Some(true)),
+            ("0 - donations welcome", Some(true)),
+            ("£5 for materials", Some(false)),
+            ("5.", Some(false)),
+            ("£5", Some(false)),
+            ("£12.50 for materials", Some(false)),
+            ("Pay what you can", None),
+            ("", None),
+        ] {
+            assert_eq!(workshop(cost, "").is_free(), expected, "{cost}");
+        }
+    }
+
+    #[test]
+    fn suitable_for_age() {
+        for (age_range, age, expected) in [
+            ("8+", 8, Some(true)),
+            ("8+", 7, Some(false)),
+            ("12 - 16", 16, Some(true)),
+            ("12–16", 17, Some(false)),
+            ("All ages", 3, Some(true)),
+            ("Adults only", 17, Some(false)),
+            ("Under 12s", 11, Some(true)),
+            ("Under 12s", 12, Some(false)),
+            ("16", 16, Some(true)),
+            ("Ask the organiser", 10, None),
+            ("", 10, None),
+        ] {
+            assert_eq!(
+                workshop("", age_range).suitable_for_age(age),
+                expected,
+                "{age_range} {age}"
+            );
+        }
+    }
+
+    #[test]
+    fn field_names() {
+        let workshop = Workshop {
+            cost: "".to_owned(),
+            equiptment: None,
+            age_range: "".to_owned(),
+            attendees: None,
+        };
+
+        let value = serde_json::to_value(workshop).unwrap();
+        let mut names: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        names.sort();
+
+        let mut expected = FIELDS.to_vec();
+        expected.sort();
+
+        assert_eq!(names, expected);
+    }
+}
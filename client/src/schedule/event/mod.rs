@@ -1,13 +1,17 @@
 mod kind;
-mod timestamp;
+pub(super) mod timestamp;
 
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use serde_with::{NoneAsEmptyString, serde_as};
 use std::cmp::Ordering;
 use url::Url;
 
-pub use self::kind::{Kind, Workshop};
+pub use self::{
+    kind::{Kind, Workshop},
+    timestamp::DEFAULT_TIMEZONE,
+};
 
 #[serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -24,6 +28,12 @@ pub struct Event {
 
     pub venue: String,
 
+    /// Canonical ID of the venue, for venues known to a
+    /// [`VenueRegistry`](super::venue::VenueRegistry) (see
+    /// [`CanonicalVenues`](super::mutation::CanonicalVenues)).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue_id: Option<String>,
+
     #[serde_as(deserialize_as = "NoneAsEmptyString")]
     pub map_link: Option<String>,
 
@@ -44,6 +54,11 @@ pub struct Event {
     pub is_family_friendly: Option<bool>,
 
     pub link: Url,
+
+    /// Fields from the upstream API that are not otherwise known about.
+    /// Kept so that they are not lost when the event is serialized again.
+    #[serde(flatten, deserialize_with = "kind::deserialize_extra_fields")]
+    pub extra: Map<String, Value>,
 }
 
 impl Event {
@@ -59,6 +74,7 @@ impl Event {
             start,
             end: start + duration,
             venue: "".to_owned(),
+            venue_id: None,
             map_link: None,
             title: "".to_owned(),
             speaker: "".to_owned(),
@@ -68,18 +84,21 @@ impl Event {
             may_record: None,
             is_family_friendly: None,
             link: Url::parse("http://example.com").unwrap(),
+            extra: Map::default(),
         }
     }
 
+    /// Where the event is in time relative to a timestamp.
+    /// Events that end before they start are treated as ending when they start.
     pub fn relative_to(&self, timestamp: DateTime<FixedOffset>) -> RelativeTime {
-        if self.end < timestamp {
+        let end = self.end.max(self.start);
+
+        if end < timestamp {
             RelativeTime::Past
-        } else if self.start <= timestamp && self.end >= timestamp {
+        } else if self.start <= timestamp {
             RelativeTime::Now
-        } else if self.start > timestamp {
-            RelativeTime::Future
         } else {
-            panic!("event start and end timestamps are quite obviously wrong");
+            RelativeTime::Future
         }
     }
 }
@@ -111,6 +130,79 @@ pub enum RelativeTime {
 mod test {
     use super::*;
     use chrono::Duration;
+    use serde_json::json;
+
+    fn event_json(kind: Value) -> Value {
+        let mut event = json!({
+            "id": 1,
+            "slug": "a-workshop",
+            "start_date": "2024-05-31T10:00:00+01:00",
+            "end_date": "2024-05-31T10:30:00+01:00",
+            "venue": "Workshop 1",
+            "map_link": null,
+            "title": "A workshop",
+            "speaker": "Someone",
+            "pronouns": null,
+            "description": "",
+            "may_record": false,
+            "is_family_friendly": true,
+            "link": "https://www.emfcamp.org/schedule/2024/1-a-workshop",
+            "content_note": "Loud noises",
+            "video": { "youtube": "https://youtube.com/watch?v=abc" },
+        });
+
+        event
+            .as_object_mut()
+            .unwrap()
+            .extend(kind.as_object().unwrap().clone());
+
+        event
+    }
+
+    #[test]
+    fn extra_fields_round_trip() {
+        let data = event_json(json!({
+            "type": "workshop",
+            "cost": "Free",
+            "equiptment": null,
+            "age_range": "All ages",
+            "attendees": "20",
+        }));
+
+        let event: Event = serde_json::from_value(data.clone()).unwrap();
+
+        assert!(matches!(event.kind, Kind::Workshop(_)));
+
+        let mut extra_names: Vec<_> = event.extra.keys().cloned().collect();
+        extra_names.sort();
+        assert_eq!(extra_names, vec!["content_note", "video"]);
+
+        assert_eq!(serde_json::to_value(&event).unwrap(), data);
+    }
+
+    #[test]
+    fn extra_fields_unknown_kind() {
+        let data = event_json(json!({
+            "type": "lightning",
+            "slots": 6,
+        }));
+
+        let event: Event = serde_json::from_value(data.clone()).unwrap();
+
+        assert_eq!(
+            event.kind,
+            Kind::Other {
+                type_name: "lightning".to_owned(),
+            }
+        );
+
+        let mut extra_names: Vec<_> = event.extra.keys().cloned().collect();
+        extra_names.sort();
+        assert_eq!(extra_names, vec!["content_note", "slots", "video"]);
+        assert_eq!(event.extra["slots"], 6);
+
+        assert_eq!(serde_json::to_value(&event).unwrap(), data);
+    }
 
     #[test]
     fn relative_time_past() {
@@ -151,13 +243,20 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    fn relative_time_now_panic() {
+    fn relative_time_ends_before_start() {
         let mut event = Event::dummy(
             0,
             DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
         );
         event.end = event.start - Duration::try_minutes(5).unwrap();
+        let second = Duration::try_seconds(1).unwrap();
+
+        assert_eq!(
+            event.relative_to(event.start - second),
+            RelativeTime::Future
+        );
         assert_eq!(event.relative_to(event.start), RelativeTime::Now);
+        assert_eq!(event.relative_to(event.end), RelativeTime::Future);
+        assert_eq!(event.relative_to(event.start + second), RelativeTime::Past);
     }
 }
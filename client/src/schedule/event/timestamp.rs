@@ -1,6 +1,10 @@
-use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeDelta};
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDateTime, Offset, TimeDelta, TimeZone};
+use chrono_tz::Tz;
 use serde::{Deserialize, Deserializer};
 
+/// The timezone the festival takes place in, used to interpret timestamps that have no offset.
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Europe::London;
+
 pub(super) fn deserialize<'de, D>(
     deserializer: D,
 ) -> std::result::Result<DateTime<FixedOffset>, D::Error>
@@ -8,10 +12,10 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    parse_timestamp_string(&s).map_err(serde::de::Error::custom)
+    parse_timestamp_string(&s, DEFAULT_TIMEZONE).map_err(serde::de::Error::custom)
 }
 
-fn parse_timestamp_string(s: &str) -> Result<DateTime<FixedOffset>, &str> {
+pub(crate) fn parse_timestamp_string(s: &str, timezone: Tz) -> Result<DateTime<FixedOffset>, &str> {
     match DateTime::parse_from_rfc3339(s) {
         Ok(t) => Ok(t),
         Err(_) => {
@@ -24,13 +28,92 @@ fn parse_timestamp_string(s: &str) -> Result<DateTime<FixedOffset>, &str> {
                     // But really, every programming language includes a library capable of handling RFC 3339 and RFC
                     // 2822, what possible reason is there for formatting timestamps in anything different.
                     // At least chrono makes correcting this fuckup easy.
-                    Ok(DateTime::from_naive_utc_and_offset(
-                        t - TimeDelta::try_hours(1).unwrap(),
-                        FixedOffset::east_opt(60 * 60).unwrap(),
-                    ))
+                    Ok(resolve_local_time(t, timezone))
                 }
                 Err(_) => Err("Failed to parse any timestamp format"),
             }
         }
     }
 }
+
+/// Converts a local time in the given timezone to an absolute time.
+///
+/// When the local time is ambiguous (i.e. the clocks went back) the earlier of the two possible times is used.
+/// When the local time does not exist (i.e. the clocks went forward) the offset from before the transition is
+/// used, resulting in a time after the transition (e.g. 01:30 becomes 02:30 on the day BST starts).
+fn resolve_local_time(t: NaiveDateTime, timezone: Tz) -> DateTime<FixedOffset> {
+    match timezone.from_local_datetime(&t) {
+        LocalResult::Single(t) => t.fixed_offset(),
+        LocalResult::Ambiguous(earliest, _) => earliest.fixed_offset(),
+        LocalResult::None => {
+            let offset = timezone
+                .offset_from_utc_datetime(&(t - TimeDelta::try_days(1).unwrap()))
+                .fix();
+            timezone
+                .from_utc_datetime(
+                    &(t - TimeDelta::try_seconds(offset.local_minus_utc().into()).unwrap()),
+                )
+                .fixed_offset()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(s: &str) -> DateTime<FixedOffset> {
+        parse_timestamp_string(s, DEFAULT_TIMEZONE).unwrap()
+    }
+
+    fn rfc3339(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    #[test]
+    fn rfc3339_passthrough() {
+        let t = parse("2024-05-31T10:00:00+02:00");
+        assert_eq!(t, rfc3339("2024-05-31T10:00:00+02:00"));
+        assert_eq!(t.offset().local_minus_utc(), 2 * 60 * 60);
+    }
+
+    #[test]
+    fn naive_summer() {
+        let t = parse("2024-05-31 10:00:00");
+        assert_eq!(t, rfc3339("2024-05-31T10:00:00+01:00"));
+        assert_eq!(t.offset().local_minus_utc(), 60 * 60);
+    }
+
+    #[test]
+    fn naive_winter() {
+        let t = parse("2024-12-31 10:00:00");
+        assert_eq!(t, rfc3339("2024-12-31T10:00:00+00:00"));
+        assert_eq!(t.offset().local_minus_utc(), 0);
+    }
+
+    #[test]
+    fn naive_ambiguous() {
+        // Clocks went back at 02:00 BST, so 01:30 happened twice
+        let t = parse("2024-10-27 01:30:00");
+        assert_eq!(t, rfc3339("2024-10-27T01:30:00+01:00"));
+    }
+
+    #[test]
+    fn naive_nonexistent() {
+        // Clocks went forward at 01:00 GMT, so 01:30 never happened
+        let t = parse("2024-03-31 01:30:00");
+        assert_eq!(t, rfc3339("2024-03-31T02:30:00+01:00"));
+    }
+
+    #[test]
+    fn naive_other_timezone() {
+        let t =
+            parse_timestamp_string("2024-05-31 10:00:00", chrono_tz::America::New_York).unwrap();
+        assert_eq!(t, rfc3339("2024-05-31T10:00:00-04:00"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_timestamp_string("half ten", DEFAULT_TIMEZONE).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use super::{Schedule, event::Event};
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A position on the site, in WGS 84 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+
+    /// Reads the position from a link to the site map, which is either a marker
+    /// (`https://map.emfcamp.org/#18/52.04/-2.37/m=52.0412,-2.3777`), the centre of the map view
+    /// (`https://map.emfcamp.org/#18/52.0412/-2.3777`) or a `geo:52.0412,-2.3777` URI.
+    pub fn from_map_link(link: &str) -> Option<Self> {
+        let link = link.trim();
+
+        if let Some(position) = link.strip_prefix("geo:") {
+            let position = position.split(';').next()?;
+            return parse_pair(position);
+        }
+
+        let (_, fragment) = link.split_once('#')?;
+        let parts: Vec<_> = fragment.split('/').collect();
+
+        if let Some(marker) = parts.iter().find_map(|part| part.strip_prefix("m=")) {
+            return parse_pair(marker);
+        }
+
+        match parts.as_slice() {
+            [_zoom, latitude, longitude, ..] => {
+                Self::new(latitude.parse().ok()?, longitude.parse().ok()?)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses a `latitude,longitude` pair.
+fn parse_pair(s: &str) -> Option<Coordinates> {
+    let (latitude, longitude) = s.split_once(',')?;
+    Coordinates::new(
+        latitude.trim().parse().ok()?,
+        longitude.trim().parse().ok()?,
+    )
+}
+
+impl Event {
+    /// The position of the event, if it has a map link that one can be read from.
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        self.map_link
+            .as_deref()
+            .and_then(Coordinates::from_map_link)
+    }
+}
+
+/// A venue and where it is, if known.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Venue {
+    pub name: String,
+    pub coordinates: Option<Coordinates>,
+}
+
+/// A GeoJSON feature collection of venues.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct VenueFeatureCollection {
+    pub features: Vec<VenueFeature>,
+}
+
+/// A GeoJSON point feature for a venue.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct VenueFeature {
+    pub geometry: Point,
+    pub properties: VenueProperties,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "Point")]
+pub struct Point {
+    /// Longitude then latitude, as GeoJSON requires.
+    pub coordinates: [f64; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueProperties {
+    pub venue: String,
+    pub now: Vec<EventSummary>,
+    pub next: Vec<EventSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventSummary {
+    pub id: u32,
+    pub title: String,
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+}
+
+impl From<&Event> for EventSummary {
+    fn from(event: &Event) -> Self {
+        Self {
+            id: event.id,
+            title: event.title.clone(),
+            start: event.start,
+            end: event.end,
+        }
+    }
+}
+
+impl Schedule {
+    /// Every venue in the schedule, with the position given by the map link of the first of its
+    /// events that has one.
+    pub fn venue_locations(&self) -> Vec<Venue> {
+        self.venues()
+            .into_iter()
+            .map(|name| {
+                let coordinates = self
+                    .events
+                    .iter()
+                    .filter(|e| e.venue == name)
+                    .find_map(|e| e.coordinates());
+                Venue { name, coordinates }
+            })
+            .collect()
+    }
+
+    /// The venues with a known position as GeoJSON points, along with what is on now and next at
+    /// each of them.
+    pub fn venues_geojson(&self, now: DateTime<FixedOffset>) -> VenueFeatureCollection {
+        let mut guide: HashMap<_, _> = self
+            .now_and_next(now)
+            .guide
+            .into_iter()
+            .map(|g| (g.venue, g.now_and_next))
+            .collect();
+
+        let features = self
+            .venue_locations()
+            .into_iter()
+            .filter_map(|venue| {
+                let coordinates = venue.coordinates?;
+                let now_and_next = guide.remove(&venue.name);
+                let (now, next) = now_and_next.map(|n| (n.now, n.next)).unwrap_or_default();
+
+                Some(VenueFeature {
+                    geometry: Point {
+                        coordinates: [coordinates.longitude, coordinates.latitude],
+                    },
+                    properties: VenueProperties {
+                        venue: venue.name,
+                        now: now.iter().map(EventSummary::from).collect(),
+                        next: next.iter().map(EventSummary::from).collect(),
+                    },
+                })
+            })
+            .collect();
+
+        VenueFeatureCollection { features }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn map_links() {
+        assert_eq!(
+            Coordinates::from_map_link("https://map.emfcamp.org/#20.24/52.0405486/-2.3781891"),
+            Coordinates::new(52.0405486, -2.3781891)
+        );
+        assert_eq!(
+            Coordinates::from_map_link("https://map.emfcamp.org/#18/52.04/-2.37/m=52.0412,-2.3777"),
+            Coordinates::new(52.0412, -2.3777)
+        );
+        assert_eq!(
+            Coordinates::from_map_link("geo:52.0412,-2.3777;u=10"),
+            Coordinates::new(52.0412, -2.3777)
+        );
+
+        assert_eq!(Coordinates::from_map_link("https://map.emfcamp.org/"), None);
+        assert_eq!(
+            Coordinates::from_map_link("https://map.emfcamp.org/#18"),
+            None
+        );
+        assert_eq!(Coordinates::from_map_link("geo:152.0,0.0"), None);
+        assert_eq!(Coordinates::from_map_link("Near the bar"), None);
+    }
+
+    fn schedule() -> Schedule {
+        let event = |id, start: &str, venue: &str, map_link: Option<&str>| {
+            let mut e = Event::dummy(id, DateTime::parse_from_rfc3339(start).unwrap());
+            e.venue = venue.to_owned();
+            e.title = format!("Event {id}");
+            e.map_link = map_link.map(str::to_owned);
+            e
+        };
+
+        Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", "Stage A", None),
+                event(
+                    1,
+                    "2024-05-31T11:00:00+01:00",
+                    "Stage A",
+                    Some("https://map.emfcamp.org/#18/52.0412/-2.3777"),
+                ),
+                event(2, "2024-05-31T10:00:00+01:00", "Stage B", None),
+            ],
+        }
+    }
+
+    #[test]
+    fn venue_locations() {
+        assert_eq!(
+            schedule().venue_locations(),
+            vec![
+                Venue {
+                    name: "Stage A".to_owned(),
+                    coordinates: Coordinates::new(52.0412, -2.3777),
+                },
+                Venue {
+                    name: "Stage B".to_owned(),
+                    coordinates: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn geojson() {
+        let now = DateTime::parse_from_rfc3339("2024-05-31T10:30:00+01:00").unwrap();
+
+        assert_eq!(
+            serde_json::to_value(schedule().venues_geojson(now)).unwrap(),
+            json!({
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [-2.3777, 52.0412],
+                        },
+                        "properties": {
+                            "venue": "Stage A",
+                            "now": [{
+                                "id": 0,
+                                "title": "Event 0",
+                                "start": "2024-05-31T10:00:00+01:00",
+                                "end": "2024-05-31T11:00:00+01:00",
+                            }],
+                            "next": [{
+                                "id": 1,
+                                "title": "Event 1",
+                                "start": "2024-05-31T11:00:00+01:00",
+                                "end": "2024-05-31T12:00:00+01:00",
+                            }],
+                        },
+                    },
+                ],
+            })
+        );
+    }
+}
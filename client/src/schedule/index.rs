@@ -0,0 +1,394 @@
+use super::{
+    Schedule,
+    event::Event,
+    now_and_next::{NowAndNext, NowAndNextBuilder},
+};
+use chrono::{DateTime, FixedOffset};
+use std::collections::BTreeMap;
+
+/// A read-only view of a schedule, indexed by time and venue so that questions such as "what is
+/// on now" can be answered without looking at every event.
+///
+/// Building the index takes some time, so it is worth it when asking many questions of the same
+/// schedule (e.g. rendering signage for every minute of the festival).
+#[derive(Debug, Clone)]
+pub struct ScheduleIndex {
+    /// Every event, in order of start time.
+    events: Vec<Event>,
+
+    all: Timeline,
+    venues: BTreeMap<String, Timeline>,
+}
+
+impl ScheduleIndex {
+    pub fn new(mut events: Vec<Event>) -> Self {
+        events.sort_by_key(|e| e.start);
+
+        let all = Timeline::new(&events, (0..events.len()).collect());
+
+        let mut by_venue: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, event) in events.iter().enumerate() {
+            by_venue.entry(event.venue.clone()).or_default().push(i);
+        }
+        let venues = by_venue
+            .into_iter()
+            .map(|(venue, indices)| (venue, Timeline::new(&events, indices)))
+            .collect();
+
+        Self {
+            events,
+            all,
+            venues,
+        }
+    }
+
+    /// Every event, in order of start time.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// The venues in the schedule, by name.
+    pub fn venues(&self) -> impl Iterator<Item = &str> {
+        self.venues.keys().map(String::as_str)
+    }
+
+    /// Events that are on at a time (see [`Event::relative_to`]), in order of start time.
+    pub fn on_at(&self, time: DateTime<FixedOffset>) -> Vec<&Event> {
+        self.overlapping(time, time)
+    }
+
+    /// Events at a venue that are on at a time, in order of start time.
+    pub fn on_at_venue(&self, venue: &str, time: DateTime<FixedOffset>) -> Vec<&Event> {
+        self.venues.get(venue).map_or_else(Vec::new, |timeline| {
+            self.resolve(timeline.overlapping(time, time))
+        })
+    }
+
+    /// Events that are on at any point between two times (inclusive), in order of start time.
+    pub fn overlapping(
+        &self,
+        from: DateTime<FixedOffset>,
+        to: DateTime<FixedOffset>,
+    ) -> Vec<&Event> {
+        self.resolve(self.all.overlapping(from, to))
+    }
+
+    /// Events that start at or after `from` and before `to`, in order of start time.
+    pub fn starting_between(
+        &self,
+        from: DateTime<FixedOffset>,
+        to: DateTime<FixedOffset>,
+    ) -> &[Event] {
+        let start = self.events.partition_point(|e| e.start < from);
+        let end = self.events.partition_point(|e| e.start < to).max(start);
+        &self.events[start..end]
+    }
+
+    /// Events at a venue that start after a time, in order of start time.
+    pub fn upcoming_at(
+        &self,
+        venue: &str,
+        time: DateTime<FixedOffset>,
+    ) -> impl Iterator<Item = &Event> {
+        let upcoming = self
+            .venues
+            .get(venue)
+            .map_or(&[][..], |timeline| timeline.starting_after(time));
+        upcoming.iter().map(|&i| &self.events[i])
+    }
+
+    /// The next events to start at a venue after a time, which all start at the same time.
+    pub fn next_after(&self, venue: &str, time: DateTime<FixedOffset>) -> Vec<&Event> {
+        let mut upcoming = self.upcoming_at(venue, time).peekable();
+        match upcoming.peek() {
+            Some(first) => {
+                let start = first.start;
+                upcoming.take_while(|e| e.start == start).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// The same as [`Schedule::now_and_next`], using the index.
+    pub fn now_and_next(&self, now: DateTime<FixedOffset>) -> NowAndNext {
+        self.now_and_next_builder(now).build()
+    }
+
+    /// The same as [`Schedule::now_and_next_builder`], using the index.
+    pub fn now_and_next_builder(&self, now: DateTime<FixedOffset>) -> NowAndNextBuilder<'_> {
+        NowAndNextBuilder::from_index(self, now)
+    }
+
+    fn resolve(&self, indices: Vec<usize>) -> Vec<&Event> {
+        indices.into_iter().map(|i| &self.events[i]).collect()
+    }
+}
+
+impl From<Schedule> for ScheduleIndex {
+    fn from(schedule: Schedule) -> Self {
+        Self::new(schedule.events)
+    }
+}
+
+impl Schedule {
+    /// Builds an index of the events, for answering many time and venue queries quickly.
+    pub fn index(&self) -> ScheduleIndex {
+        ScheduleIndex::new(self.events.clone())
+    }
+}
+
+/// A set of events in order of start time, along with a tree holding the latest end time of each
+/// range of them, so that events that are still on can be found without checking every event
+/// that started earlier.
+#[derive(Debug, Clone)]
+struct Timeline {
+    /// Indices of the events, in order of start time.
+    events: Vec<usize>,
+
+    starts: Vec<DateTime<FixedOffset>>,
+
+    /// Latest end of the events in each node of a segment tree over `events`, where node 1 is
+    /// the root and the children of node `n` are `2n` and `2n + 1`.
+    latest_end: Vec<DateTime<FixedOffset>>,
+}
+
+impl Timeline {
+    fn new(all: &[Event], events: Vec<usize>) -> Self {
+        let starts: Vec<_> = events.iter().map(|&i| all[i].start).collect();
+        // Events that end before they start are treated as ending when they start
+        let ends: Vec<_> = events
+            .iter()
+            .map(|&i| all[i].end.max(all[i].start))
+            .collect();
+
+        let mut latest_end = match ends.first() {
+            Some(&end) => vec![end; 4 * ends.len()],
+            None => Vec::new(),
+        };
+        if !ends.is_empty() {
+            build(&mut latest_end, &ends, 1, 0, ends.len());
+        }
+
+        Self {
+            events,
+            starts,
+            latest_end,
+        }
+    }
+
+    fn starting_after(&self, time: DateTime<FixedOffset>) -> &[usize] {
+        &self.events[self.starts.partition_point(|&s| s <= time)..]
+    }
+
+    /// Events that start at or before `to` and end at or after `from`.
+    fn overlapping(&self, from: DateTime<FixedOffset>, to: DateTime<FixedOffset>) -> Vec<usize> {
+        let started = self.starts.partition_point(|&s| s <= to);
+
+        let mut found = Vec::new();
+        if started > 0 {
+            self.collect(1, 0, self.events.len(), started, from, &mut found);
+        }
+        found
+    }
+
+    /// Collects the events in positions `lo..hi` (covered by `node`) that are before `limit` and
+    /// end at or after `from`.
+    fn collect(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        limit: usize,
+        from: DateTime<FixedOffset>,
+        found: &mut Vec<usize>,
+    ) {
+        if lo >= limit || self.latest_end[node] < from {
+            return;
+        }
+
+        if hi - lo == 1 {
+            found.push(self.events[lo]);
+        } else {
+            let mid = (lo + hi) / 2;
+            self.collect(2 * node, lo, mid, limit, from, found);
+            self.collect(2 * node + 1, mid, hi, limit, from, found);
+        }
+    }
+}
+
+fn build(
+    tree: &mut [DateTime<FixedOffset>],
+    ends: &[DateTime<FixedOffset>],
+    node: usize,
+    lo: usize,
+    hi: usize,
+) -> DateTime<FixedOffset> {
+    let latest = if hi - lo == 1 {
+        ends[lo]
+    } else {
+        let mid = (lo + hi) / 2;
+        build(tree, ends, 2 * node, lo, mid).max(build(tree, ends, 2 * node + 1, mid, hi))
+    };
+    tree[node] = latest;
+    latest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        schedule::event::RelativeTime,
+        testing::{event, time},
+    };
+    use chrono::Duration;
+
+    fn schedule() -> Schedule {
+        Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", 60, "Stage A"),
+                event(1, "2024-05-31T11:00:00+01:00", 30, "Stage A"),
+                event(2, "2024-05-31T11:00:00+01:00", 45, "Stage A"),
+                event(3, "2024-05-31T09:00:00+01:00", 480, "Bar"),
+                event(4, "2024-05-31T10:30:00+01:00", 60, "Stage B"),
+                event(5, "2024-05-31T12:00:00+01:00", -10, "Stage B"),
+                event(6, "2024-05-31T14:00:00+01:00", 60, "Stage A"),
+            ],
+        }
+    }
+
+    fn ids<'a>(events: impl IntoIterator<Item = &'a Event>) -> Vec<u32> {
+        events.into_iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn on_at() {
+        let index = schedule().index();
+
+        assert_eq!(
+            ids(index.on_at(time("2024-05-31T08:00:00+01:00"))),
+            Vec::<u32>::new()
+        );
+        assert_eq!(
+            ids(index.on_at(time("2024-05-31T10:45:00+01:00"))),
+            vec![3, 0, 4]
+        );
+        assert_eq!(
+            ids(index.on_at(time("2024-05-31T11:00:00+01:00"))),
+            vec![3, 0, 4, 1, 2]
+        );
+        assert_eq!(
+            ids(index.on_at(time("2024-05-31T12:00:00+01:00"))),
+            vec![3, 5]
+        );
+
+        assert_eq!(
+            ids(index.on_at_venue("Stage A", time("2024-05-31T11:40:00+01:00"))),
+            vec![2]
+        );
+        assert_eq!(
+            ids(index.on_at_venue("Nowhere", time("2024-05-31T11:40:00+01:00"))),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn on_at_matches_relative_time() {
+        let schedule = schedule();
+        let index = schedule.index();
+
+        let mut t = time("2024-05-31T08:00:00+01:00");
+        while t < time("2024-05-31T18:00:00+01:00") {
+            let mut expected: Vec<_> = schedule
+                .events
+                .iter()
+                .filter(|e| e.relative_to(t) == RelativeTime::Now)
+                .collect();
+            expected.sort_by_key(|e| e.start);
+
+            assert_eq!(ids(index.on_at(t)), ids(expected), "at {t}");
+            t += Duration::try_minutes(5).unwrap();
+        }
+    }
+
+    #[test]
+    fn next_after() {
+        let index = schedule().index();
+
+        assert_eq!(
+            ids(index.next_after("Stage A", time("2024-05-31T10:30:00+01:00"))),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(index.next_after("Stage A", time("2024-05-31T11:00:00+01:00"))),
+            vec![6]
+        );
+        assert_eq!(
+            ids(index.next_after("Stage A", time("2024-05-31T14:00:00+01:00"))),
+            Vec::<u32>::new()
+        );
+        assert_eq!(
+            ids(index.upcoming_at("Stage A", time("2024-05-31T09:00:00+01:00"))),
+            vec![0, 1, 2, 6]
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let index = schedule().index();
+
+        assert_eq!(
+            ids(index.starting_between(
+                time("2024-05-31T10:00:00+01:00"),
+                time("2024-05-31T12:00:00+01:00")
+            )),
+            vec![0, 4, 1, 2]
+        );
+        assert_eq!(
+            ids(index.starting_between(
+                time("2024-05-31T12:00:00+01:00"),
+                time("2024-05-31T10:00:00+01:00")
+            )),
+            Vec::<u32>::new()
+        );
+        assert_eq!(
+            ids(index.overlapping(
+                time("2024-05-31T11:50:00+01:00"),
+                time("2024-05-31T14:00:00+01:00")
+            )),
+            vec![3, 5, 6]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let index = Schedule { events: vec![] }.index();
+
+        assert_eq!(index.venues().count(), 0);
+        assert!(index.on_at(time("2024-05-31T10:00:00+01:00")).is_empty());
+        assert!(
+            index
+                .now_and_next(time("2024-05-31T10:00:00+01:00"))
+                .guide
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn now_and_next_matches_scan() {
+        let schedule = schedule();
+        let index = schedule.index();
+
+        let mut t = time("2024-05-31T08:00:00+01:00");
+        while t < time("2024-05-31T18:00:00+01:00") {
+            let scanned = schedule.now_and_next_builder(t).slots(3).build();
+            let indexed = index.now_and_next_builder(t).slots(3).build();
+
+            assert_eq!(
+                serde_json::to_value(&scanned).unwrap(),
+                serde_json::to_value(&indexed).unwrap(),
+                "at {t}"
+            );
+            t += Duration::try_minutes(5).unwrap();
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Rendering of the markdown used in event descriptions.
+
+use super::event::Event;
+use pulldown_cmark::{CowStr, Event as MdEvent, Options, Parser, Tag, TagEnd};
+use std::io;
+use termcolor::{Color, ColorSpec, NoColor, WriteColor};
+
+const ELLIPSIS: char = '…';
+
+/// URL schemes that links and images may use in rendered HTML, any other scheme (e.g.
+/// `javascript:`) is removed.
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+fn parser(markdown: &str) -> Parser<'_> {
+    Parser::new_ext(
+        markdown,
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS,
+    )
+}
+
+/// Renders markdown as plain text, keeping paragraphs and list items on their own lines and
+/// giving the target of links in brackets.
+pub fn to_plain_text(markdown: &str) -> String {
+    let mut out = NoColor::new(Vec::new());
+    write_styled(&mut out, markdown).expect("writing to a Vec does not fail");
+    String::from_utf8(out.into_inner()).expect("rendered text is valid UTF-8")
+}
+
+/// The first paragraph of the markdown as plain text on a single line, shortened at a word
+/// boundary to at most `max_len` characters if needed.
+pub fn summary(markdown: &str, max_len: usize) -> String {
+    let text = to_plain_text(markdown);
+    let paragraph = text.split("\n\n").next().unwrap_or_default();
+    let paragraph = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if paragraph.chars().count() <= max_len {
+        return paragraph;
+    }
+
+    // Leave room for the ellipsis
+    let end = paragraph
+        .char_indices()
+        .nth(max_len.saturating_sub(1))
+        .map_or(paragraph.len(), |(i, _)| i);
+    let prefix = &paragraph[..end];
+
+    // Break at the last space if the cut falls within a word
+    let cut = match prefix.rfind(' ') {
+        Some(i) if i > 0 && !paragraph[end..].starts_with(' ') => i,
+        _ => end,
+    };
+
+    let mut summary = paragraph[..cut].trim_end().to_owned();
+    summary.push(ELLIPSIS);
+    summary
+}
+
+/// Renders markdown as HTML that is safe to include in a page: any HTML in the markdown is escaped
+/// so that it shows as text, and links and images are only kept if they use a safe URL scheme.
+pub fn to_html(markdown: &str) -> String {
+    let events = parser(markdown).map(|event| match event {
+        MdEvent::Html(html) | MdEvent::InlineHtml(html) => MdEvent::Text(html),
+        MdEvent::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => MdEvent::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        MdEvent::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => MdEvent::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events);
+    html
+}
+
+/// The URL if it is relative or uses one of [`SAFE_SCHEMES`], otherwise an empty URL.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = url
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+
+    match scheme {
+        Some(scheme) if !SAFE_SCHEMES.contains(&scheme.to_lowercase().as_str()) => {
+            CowStr::Borrowed("")
+        }
+        _ => url,
+    }
+}
+
+/// Renders markdown as text styled for a terminal.
+pub fn write_styled<W: WriteColor>(out: &mut W, markdown: &str) -> io::Result<()> {
+    let mut renderer = Renderer {
+        out,
+        style: Style::default(),
+        lists: Vec::new(),
+        links: Vec::new(),
+        pending_newlines: 0,
+        at_start: true,
+    };
+
+    for event in parser(markdown) {
+        renderer.event(event)?;
+    }
+
+    renderer.out.reset()
+}
+
+#[derive(Debug, Default)]
+struct Style {
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    heading: usize,
+    code: usize,
+    link: usize,
+}
+
+impl Style {
+    fn color_spec(&self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        spec.set_bold(self.strong > 0 || self.heading > 0)
+            .set_italic(self.emphasis > 0)
+            .set_underline(self.link > 0 || self.heading > 0)
+            .set_strikethrough(self.strikethrough > 0);
+
+        if self.code > 0 {
+            spec.set_fg(Some(Color::Cyan));
+        } else if self.link > 0 {
+            spec.set_fg(Some(Color::Blue));
+        }
+
+        spec
+    }
+}
+
+struct Link {
+    destination: String,
+    text: String,
+}
+
+struct Renderer<'a, W> {
+    out: &'a mut W,
+    style: Style,
+
+    /// The next number of each (nested) list being rendered, `None` for unordered lists.
+    lists: Vec<Option<u64>>,
+
+    links: Vec<Link>,
+
+    /// Line breaks to write before any more text, so that nothing trails the final text.
+    pending_newlines: usize,
+    at_start: bool,
+}
+
+impl<W: WriteColor> Renderer<'_, W> {
+    fn event(&mut self, event: MdEvent) -> io::Result<()> {
+        match event {
+            MdEvent::Start(tag) => self.start(tag)?,
+            MdEvent::End(tag) => self.end(tag)?,
+            MdEvent::Text(text) => self.text(&text)?,
+            MdEvent::Code(code) => {
+                self.style.code += 1;
+                self.text(&code)?;
+                self.style.code -= 1;
+            }
+            MdEvent::SoftBreak => self.text(" ")?,
+            MdEvent::HardBreak => self.newlines(1),
+            MdEvent::Rule => {
+                self.newlines(2);
+                self.text("---")?;
+                self.newlines(2);
+            }
+            MdEvent::TaskListMarker(checked) => self.text(if checked { "[x] " } else { "[ ] " })?,
+            MdEvent::FootnoteReference(name) => self.text(&format!("[{name}]"))?,
+            MdEvent::Html(_)
+            | MdEvent::InlineHtml(_)
+            | MdEvent::InlineMath(_)
+            | MdEvent::DisplayMath(_) => {}
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, tag: Tag) -> io::Result<()> {
+        match tag {
+            Tag::Paragraph | Tag::BlockQuote(_) | Tag::CodeBlock(_) | Tag::Table(_) => {
+                self.newlines(2);
+                if matches!(tag, Tag::CodeBlock(_)) {
+                    self.style.code += 1;
+                }
+            }
+            Tag::Heading { .. } => {
+                self.newlines(2);
+                self.style.heading += 1;
+            }
+            Tag::List(first) => {
+                self.newlines(1);
+                self.lists.push(first);
+            }
+            Tag::Item => {
+                self.newlines(1);
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "- ".to_owned(),
+                };
+                self.text(&format!("{}{marker}", "  ".repeat(depth)))?;
+            }
+            Tag::Emphasis => self.style.emphasis += 1,
+            Tag::Strong => self.style.strong += 1,
+            Tag::Strikethrough => self.style.strikethrough += 1,
+            Tag::Link { dest_url, .. } => {
+                self.style.link += 1;
+                self.links.push(Link {
+                    destination: dest_url.into_string(),
+                    text: String::new(),
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, tag: TagEnd) -> io::Result<()> {
+        match tag {
+            TagEnd::Paragraph | TagEnd::BlockQuote(_) | TagEnd::Table => self.newlines(2),
+            TagEnd::CodeBlock => {
+                self.style.code -= 1;
+                self.newlines(2);
+            }
+            TagEnd::Heading(_) => {
+                self.style.heading -= 1;
+                self.newlines(2);
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.newlines(if self.lists.is_empty() { 2 } else { 1 });
+            }
+            TagEnd::TableHead | TagEnd::TableRow => self.newlines(1),
+            TagEnd::TableCell => self.text("  ")?,
+            TagEnd::Emphasis => self.style.emphasis -= 1,
+            TagEnd::Strong => self.style.strong -= 1,
+            TagEnd::Strikethrough => self.style.strikethrough -= 1,
+            TagEnd::Link => {
+                self.style.link -= 1;
+                if let Some(link) = self.links.pop()
+                    && link.text.trim() != link.destination
+                    && !link.destination.is_empty()
+                {
+                    self.text(&format!(" ({})", link.destination))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn newlines(&mut self, n: usize) {
+        self.pending_newlines = self.pending_newlines.max(n);
+    }
+
+    fn text(&mut self, text: &str) -> io::Result<()> {
+        // Code blocks end with a line break, which is handled by the end of the block instead
+        let text = if self.style.code > 0 {
+            text.trim_end_matches('\n')
+        } else {
+            text
+        };
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        if !self.at_start {
+            for _ in 0..self.pending_newlines {
+                writeln!(self.out)?;
+            }
+        }
+        self.pending_newlines = 0;
+        self.at_start = false;
+
+        for link in &mut self.links {
+            link.text.push_str(text);
+        }
+
+        self.out.set_color(&self.style.color_spec())?;
+        write!(self.out, "{text}")?;
+        self.out.reset()
+    }
+}
+
+impl Event {
+    /// The description as plain text, see [`to_plain_text`].
+    pub fn description_text(&self) -> String {
+        to_plain_text(&self.description)
+    }
+
+    /// A short summary of the description, see [`summary`].
+    pub fn description_summary(&self, max_len: usize) -> String {
+        summary(&self.description, max_len)
+    }
+
+    /// The description as sanitised HTML, see [`to_html`].
+    pub fn description_html(&self) -> String {
+        to_html(&self.description)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use termcolor::Buffer;
+
+    const DESCRIPTION: &str = "# Intro\r\n\r\nLearn **LoRa** with *us*,\r\nsee [the wiki](https://example.com/wiki).\r\n\r\n- one\r\n- two\r\n\r\n1. first\r\n2. second\r\n\r\n<script>alert(1)</script>\r\n\r\n`code` and https://example.com";
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            to_plain_text(DESCRIPTION),
+            "Intro\n\nLearn LoRa with us, see the wiki (https://example.com/wiki).\n\n- one\n- two\n\n1. first\n2. second\n\ncode and https://example.com"
+        );
+    }
+
+    #[test]
+    fn plain_text_of_plain_text() {
+        assert_eq!(to_plain_text("Just some text."), "Just some text.");
+        assert_eq!(to_plain_text(""), "");
+    }
+
+    #[test]
+    fn nested_lists() {
+        assert_eq!(
+            to_plain_text("- one\n  - one a\n  - one b\n- two"),
+            "- one\n  - one a\n  - one b\n- two"
+        );
+    }
+
+    #[test]
+    fn summaries() {
+        assert_eq!(summary(DESCRIPTION, 100), "Intro");
+        assert_eq!(
+            summary("Learn **LoRa** with us,\nthe easy way.\n\nMore.", 100),
+            "Learn LoRa with us, the easy way."
+        );
+        assert_eq!(
+            summary("Learn **LoRa** with us, the easy way.", 20),
+            "Learn LoRa with us,…"
+        );
+        assert_eq!(summary("Supercalifragilistic", 6), "Super…");
+        assert_eq!(summary("", 6), "");
+    }
+
+    #[test]
+    fn summaries_of_non_ascii_text() {
+        assert_eq!(
+            summary("Supercalifragilisticé expialidocious", 21),
+            "Supercalifragilistic…"
+        );
+        assert_eq!(
+            summary("Supercalifragilisticé expialidocious", 22),
+            "Supercalifragilisticé…"
+        );
+        assert_eq!(summary("Café crème brûlée", 10), "Café…");
+        assert_eq!(summary("ééééé", 3), "éé…");
+        assert_eq!(summary("Learn to sew 🧵 with us", 15), "Learn to sew 🧵…");
+    }
+
+    #[test]
+    fn html() {
+        assert_eq!(
+            to_html("Learn **LoRa** with [us](https://example.com)."),
+            "<p>Learn <strong>LoRa</strong> with <a href=\"https://example.com\">us</a>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        assert_eq!(
+            to_html("<script>alert(1)</script>\n\nHi <img src=x onerror=alert(1)>"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;\n<p>Hi &lt;img src=x onerror=alert(1)&gt;</p>\n"
+        );
+    }
+
+    #[test]
+    fn unsafe_urls_are_removed() {
+        assert_eq!(
+            to_html(
+                "[a](javascript:alert(1)) [b](JavaScript:alert(1)) ![c](data:image/png;base64,AAAA)"
+            ),
+            "<p><a href=\"\">a</a> <a href=\"\">b</a> <img src=\"\" alt=\"c\" /></p>\n"
+        );
+        assert_eq!(
+            to_html("[a](/schedule/2024) [b](mailto:a@example.com) [c](page?x=a:b)"),
+            "<p><a href=\"/schedule/2024\">a</a> <a href=\"mailto:a@example.com\">b</a> <a href=\"page?x=a:b\">c</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn styled() {
+        let mut out = Buffer::ansi();
+        write_styled(&mut out, "Learn **LoRa**").unwrap();
+        let styled = String::from_utf8(out.into_inner()).unwrap();
+
+        assert!(styled.contains("\x1b[1m"));
+        assert!(styled.contains("LoRa"));
+        assert_ne!(styled, "Learn LoRa");
+    }
+}
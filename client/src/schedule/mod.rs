@@ -1,12 +1,27 @@
+pub mod day;
+pub mod decode;
+pub mod diff;
 pub mod event;
+pub mod geo;
+pub mod index;
+pub mod markdown;
 pub mod mutation;
 pub mod now_and_next;
+pub mod query;
+pub mod search;
+pub mod sessions;
+mod snapshot;
+pub mod speakers;
+pub mod validate;
+pub mod venue;
 
 use self::mutation::Mutators;
 use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
 pub struct Schedule {
     pub events: Vec<event::Event>,
 }
@@ -30,4 +45,12 @@ impl Schedule {
     pub fn now_and_next(&self, now: DateTime<FixedOffset>) -> now_and_next::NowAndNext {
         now_and_next::NowAndNext::new(&self.events, now)
     }
+
+    /// Builds a now and next that can also list events later than the next ones.
+    pub fn now_and_next_builder(
+        &self,
+        now: DateTime<FixedOffset>,
+    ) -> now_and_next::NowAndNextBuilder<'_> {
+        now_and_next::NowAndNextBuilder::new(&self.events, now)
+    }
 }
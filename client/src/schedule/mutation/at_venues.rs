@@ -1,5 +1,7 @@
-use super::{Event, Mutator};
+use super::{Event, Mutator, MutatorSpec};
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AtVenues {
     venues: Vec<String>,
 }
@@ -14,6 +16,10 @@ impl Mutator for AtVenues {
     fn mutate(&self, events: &mut Vec<Event>) {
         events.retain(|event| self.venues.contains(&event.venue));
     }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::AtVenues(self.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,65 @@
+use super::{Event, Mutator, MutatorSpec, Unknown};
+use serde::{Deserialize, Serialize};
+
+/// Keeps events with a speaker that contains any of the given names, ignoring case (so that e.g.
+/// "Alice" matches an event given by "Alice & Bob").
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BySpeakers {
+    speakers: Vec<String>,
+
+    /// What to do with events that have no speaker.
+    #[serde(default)]
+    unknown: Unknown,
+}
+
+impl BySpeakers {
+    pub fn new(speakers: Vec<String>, unknown: Unknown) -> Self {
+        Self { speakers, unknown }
+    }
+}
+
+impl Mutator for BySpeakers {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        let speakers: Vec<_> = self.speakers.iter().map(|s| s.to_lowercase()).collect();
+
+        events.retain(|event| {
+            let speaker = event.speaker.trim().to_lowercase();
+            self.unknown
+                .keep((!speaker.is_empty()).then(|| speakers.iter().any(|s| speaker.contains(s))))
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::BySpeakers(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = ["Alice & Bob", "Carol", "bob", ""]
+            .into_iter()
+            .enumerate()
+            .map(|(id, speaker)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.speaker = speaker.to_owned();
+                e
+            })
+            .collect();
+
+        let mut mutated = events.clone();
+        BySpeakers::new(vec!["Bob".to_owned()], Unknown::Exclude).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone(), events[2].clone()]);
+
+        let mut mutated = events.clone();
+        BySpeakers::new(vec!["carol".to_owned()], Unknown::Include).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[1].clone(), events[3].clone()]);
+    }
+}
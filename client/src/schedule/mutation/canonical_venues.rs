@@ -0,0 +1,102 @@
+use super::{Event, Mutator, MutatorSpec};
+use crate::schedule::venue::VenueRegistry;
+use serde::{Deserialize, Serialize};
+
+/// Renames venues that are known to a registry to the name the registry gives them, and sets their
+/// canonical ID, so that differently spelled venues are treated as one.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CanonicalVenues {
+    #[serde(flatten)]
+    registry: VenueRegistry,
+}
+
+impl CanonicalVenues {
+    pub fn new(registry: VenueRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Mutator for CanonicalVenues {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        for event in events.iter_mut() {
+            if let Some(venue) = self.registry.find(&event.venue) {
+                event.venue = venue.name.clone();
+                event.venue_id = Some(venue.id.clone());
+            }
+        }
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::CanonicalVenues(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::venue::VenueInfo;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = ["stage a", "Main Stage", "Stage B"]
+            .into_iter()
+            .enumerate()
+            .map(|(id, venue)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.venue = venue.to_owned();
+                e
+            })
+            .collect();
+
+        let mutator = CanonicalVenues::new(VenueRegistry {
+            venues: vec![VenueInfo {
+                id: "stage-a".to_owned(),
+                name: "Stage A".to_owned(),
+                aliases: vec!["Main Stage".to_owned()],
+                area: None,
+                order: None,
+            }],
+        });
+
+        let mut mutated = events.clone();
+        mutator.mutate(&mut mutated);
+
+        let venues: Vec<_> = mutated.iter().map(|e| e.venue.as_str()).collect();
+        assert_eq!(venues, vec!["Stage A", "Stage A", "Stage B"]);
+
+        let ids: Vec<_> = mutated.iter().map(|e| e.venue_id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("stage-a"), Some("stage-a"), None]);
+    }
+
+    #[test]
+    fn spec() {
+        let spec: MutatorSpec = toml::from_str(
+            r#"
+            type = "canonical_venues"
+
+            [[venues]]
+            id = "stage-a"
+            name = "Stage A"
+            aliases = ["Main Stage"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec,
+            MutatorSpec::CanonicalVenues(CanonicalVenues::new(VenueRegistry {
+                venues: vec![VenueInfo {
+                    id: "stage-a".to_owned(),
+                    name: "Stage A".to_owned(),
+                    aliases: vec!["Main Stage".to_owned()],
+                    area: None,
+                    order: None,
+                }],
+            }))
+        );
+    }
+}
@@ -1,34 +1,115 @@
-use super::{Event, Mutator};
-use chrono::{DateTime, FixedOffset};
+use super::{Event, Mutator, MutatorSpec};
+use chrono::{DateTime, FixedOffset, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-/// Offsets event timestamps based on the difference between the start time of the first event and a given time.
-/// Effectively making EMF start at a time of your choosing.
+/// Offsets event timestamps based on the difference between the start time of the earliest event
+/// and a given time, optionally also speeding up (or slowing down) the schedule.
+/// Effectively making EMF start at a time (and run at a pace) of your choosing.
 /// Useful for development only.
-/// Note that the events must be sorted by start timestamp before using this mutator.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct FakeStartEpoch {
     epoch: DateTime<FixedOffset>,
+
+    #[serde(default, skip_serializing_if = "TimeScale::is_real_time")]
+    time_scale: TimeScale,
 }
 
 impl FakeStartEpoch {
     pub fn new(epoch: DateTime<FixedOffset>) -> Self {
-        Self { epoch }
+        Self {
+            epoch,
+            time_scale: TimeScale::default(),
+        }
+    }
+
+    pub fn with_time_scale(mut self, time_scale: TimeScale) -> Self {
+        self.time_scale = time_scale;
+        self
     }
 }
 
 impl Mutator for FakeStartEpoch {
     fn mutate(&self, events: &mut Vec<Event>) {
-        if let Some(first_event) = events.first() {
-            let first_event_start_time = first_event.start;
-
+        if let Some(first_event_start_time) = events.iter().map(|e| e.start).min() {
             for event in events.iter_mut() {
                 let offset = event.start - first_event_start_time;
-                event.start = self.epoch + offset;
+                event.start = self.epoch + self.time_scale.scale(offset);
 
                 let offset = event.end - first_event_start_time;
-                event.end = self.epoch + offset;
+                event.end = self.epoch + self.time_scale.scale(offset);
             }
         }
     }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::FakeStartEpoch(self.clone()))
+    }
+}
+
+/// How many times faster than real time the schedule runs, e.g. 72 plays three days of events in
+/// one hour and 0.5 plays them at half speed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(into = "f64")]
+pub struct TimeScale(f64);
+
+impl TimeScale {
+    fn is_real_time(&self) -> bool {
+        self.0 == 1.0
+    }
+
+    fn scale(&self, duration: TimeDelta) -> TimeDelta {
+        if self.is_real_time() {
+            duration
+        } else {
+            TimeDelta::milliseconds((duration.num_milliseconds() as f64 / self.0).round() as i64)
+        }
+    }
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+// Only finite, positive values can be constructed, so equality is reflexive
+impl Eq for TimeScale {}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("time scale must be a positive number, not {0}")]
+pub struct InvalidTimeScale(f64);
+
+impl TryFrom<f64> for TimeScale {
+    type Error = InvalidTimeScale;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_finite() && value > 0.0 {
+            Ok(Self(value))
+        } else {
+            Err(InvalidTimeScale(value))
+        }
+    }
+}
+
+impl From<TimeScale> for f64 {
+    fn from(time_scale: TimeScale) -> Self {
+        time_scale.0
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeScale {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x", self.0)
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +167,69 @@ mod test {
             DateTime::parse_from_rfc3339("2024-02-01T15:00:00+00:00").unwrap()
         );
     }
+
+    #[test]
+    fn unsorted() {
+        let t = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        let events = vec![
+            Event::dummy(0, t("2024-03-12T22:00:00+00:00")),
+            Event::dummy(1, t("2024-03-12T20:00:00+00:00")),
+        ];
+
+        let mut mutated = events.clone();
+        FakeStartEpoch::new(t("2024-02-01T12:00:00+00:00")).mutate(&mut mutated);
+
+        assert_eq!(mutated[0].start, t("2024-02-01T14:00:00+00:00"));
+        assert_eq!(mutated[1].start, t("2024-02-01T12:00:00+00:00"));
+    }
+
+    #[test]
+    fn time_scale() {
+        let t = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        let events = vec![
+            Event::dummy(0, t("2024-03-12T20:00:00+00:00")),
+            Event::dummy(1, t("2024-03-15T20:00:00+00:00")),
+        ];
+
+        let mut mutated = events.clone();
+        FakeStartEpoch::new(t("2024-02-01T12:00:00+00:00"))
+            .with_time_scale(72.0.try_into().unwrap())
+            .mutate(&mut mutated);
+
+        assert_eq!(mutated[0].start, t("2024-02-01T12:00:00+00:00"));
+        assert_eq!(mutated[0].end, t("2024-02-01T12:00:50+00:00"));
+        assert_eq!(mutated[1].start, t("2024-02-01T13:00:00+00:00"));
+        assert_eq!(mutated[1].end, t("2024-02-01T13:00:50+00:00"));
+    }
+
+    #[test]
+    fn invalid_time_scale() {
+        assert!(TimeScale::try_from(0.0).is_err());
+        assert!(TimeScale::try_from(-2.0).is_err());
+        assert!(TimeScale::try_from(f64::NAN).is_err());
+
+        assert!(
+            serde_json::from_str::<FakeStartEpoch>(
+                r#"{"epoch": "2024-02-01T12:00:00+00:00", "time_scale": 0}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let mutator =
+            FakeStartEpoch::new(DateTime::parse_from_rfc3339("2024-02-01T12:00:00+00:00").unwrap())
+                .with_time_scale(TimeScale::try_from(60.0).unwrap());
+
+        let json = serde_json::to_value(&mutator).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "epoch": "2024-02-01T12:00:00Z", "time_scale": 60.0 })
+        );
+        assert_eq!(
+            serde_json::from_value::<FakeStartEpoch>(json).unwrap(),
+            mutator
+        );
+    }
 }
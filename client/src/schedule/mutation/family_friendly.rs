@@ -0,0 +1,67 @@
+use super::{Event, Mutator, MutatorSpec, Unknown};
+use serde::{Deserialize, Serialize};
+
+/// Keeps events that are (or are not) family friendly.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FamilyFriendly {
+    family_friendly: bool,
+
+    /// What to do with events that do not say if they are family friendly.
+    #[serde(default)]
+    unknown: Unknown,
+}
+
+impl FamilyFriendly {
+    pub fn new(family_friendly: bool, unknown: Unknown) -> Self {
+        Self {
+            family_friendly,
+            unknown,
+        }
+    }
+}
+
+impl Mutator for FamilyFriendly {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.unknown.keep(
+                event
+                    .is_family_friendly
+                    .map(|family_friendly| family_friendly == self.family_friendly),
+            )
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::FamilyFriendly(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = [Some(true), Some(false), None]
+            .into_iter()
+            .enumerate()
+            .map(|(id, family_friendly)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.is_family_friendly = family_friendly;
+                e
+            })
+            .collect();
+
+        let mut mutated = events.clone();
+        FamilyFriendly::new(true, Unknown::Exclude).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone()]);
+
+        let mut mutated = events.clone();
+        FamilyFriendly::new(false, Unknown::Include).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[1].clone(), events[2].clone()]);
+    }
+}
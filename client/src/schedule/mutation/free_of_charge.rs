@@ -0,0 +1,76 @@
+use super::{Event, Mutator, MutatorSpec, Unknown};
+use serde::{Deserialize, Serialize};
+
+/// Keeps workshops that are (or are not) free to attend, see [`Workshop::is_free`].
+///
+/// [`Workshop::is_free`]: crate::schedule::event::Workshop::is_free
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FreeOfCharge {
+    free: bool,
+
+    /// What to do with events that are not workshops or have a cost that cannot be understood.
+    #[serde(default)]
+    unknown: Unknown,
+}
+
+impl FreeOfCharge {
+    pub fn new(free: bool, unknown: Unknown) -> Self {
+        Self { free, unknown }
+    }
+}
+
+impl Mutator for FreeOfCharge {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.unknown.keep(
+                event
+                    .kind
+                    .workshop()
+                    .and_then(|workshop| workshop.is_free())
+                    .map(|free| free == self.free),
+            )
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::FreeOfCharge(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::{Kind, Workshop};
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = [Some("Free"), Some("£5"), Some("Ask"), None]
+            .into_iter()
+            .enumerate()
+            .map(|(id, cost)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                if let Some(cost) = cost {
+                    e.kind = Kind::Workshop(Workshop {
+                        cost: cost.to_owned(),
+                        equiptment: None,
+                        age_range: "".to_owned(),
+                        attendees: None,
+                    });
+                }
+                e
+            })
+            .collect();
+
+        let mut mutated = events.clone();
+        FreeOfCharge::new(true, Unknown::Exclude).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone()]);
+
+        let mut mutated = events.clone();
+        FreeOfCharge::new(false, Unknown::Include).mutate(&mut mutated);
+        assert_eq!(mutated, events[1..].to_vec());
+    }
+}
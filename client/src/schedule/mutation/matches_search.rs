@@ -0,0 +1,78 @@
+use super::{Event, Mutator, MutatorSpec};
+use crate::schedule::search::SearchIndex;
+use serde::{Deserialize, Serialize};
+
+/// Keeps only events that match a full text search (see [`SearchIndex`]).
+/// The order of the events is not changed, use [`SearchIndex::search`] to rank them by relevance.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MatchesSearch {
+    query: String,
+}
+
+impl MatchesSearch {
+    pub fn new(query: String) -> Self {
+        Self { query }
+    }
+}
+
+impl Mutator for MatchesSearch {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        let mut matching = vec![false; events.len()];
+        for ranked in SearchIndex::new(events).ranked(&self.query) {
+            matching[ranked.event] = true;
+        }
+
+        let mut matching = matching.into_iter();
+        events.retain(|_| matching.next().unwrap());
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::MatchesSearch(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events = vec![
+            {
+                let mut e = Event::dummy(
+                    0,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.title = "Building LoRa gateways".to_owned();
+                e
+            },
+            {
+                let mut e = Event::dummy(
+                    1,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.title = "Soldering for beginners".to_owned();
+                e
+            },
+            {
+                let mut e = Event::dummy(
+                    2,
+                    DateTime::parse_from_rfc3339("2024-03-12T21:00:00+00:00").unwrap(),
+                );
+                e.description = "A gateway to the world of radio".to_owned();
+                e
+            },
+        ];
+
+        let mutator = MatchesSearch::new("gateway".to_owned());
+
+        let mut mutated = events.clone();
+        mutator.mutate(&mut mutated);
+
+        assert_eq!(mutated.len(), 2);
+
+        assert_eq!(mutated[0], events[0]);
+        assert_eq!(mutated[1], events[2]);
+    }
+}
@@ -1,16 +1,58 @@
 mod at_venues;
+mod by_speakers;
+mod canonical_venues;
 mod ends_after;
 mod fake_start_epoch;
+mod family_friendly;
+mod free_of_charge;
+mod matches_search;
+mod of_kinds;
+mod on_day;
+mod recordable;
 mod sorted_by_start_time;
+mod spec;
 mod starts_after;
 mod starts_before;
+mod suitable_for_age;
 
 pub use self::{
-    at_venues::AtVenues, ends_after::EndsAfter, fake_start_epoch::FakeStartEpoch,
-    sorted_by_start_time::SortedByStartTime, starts_after::StartsAfter,
+    at_venues::AtVenues,
+    by_speakers::BySpeakers,
+    canonical_venues::CanonicalVenues,
+    ends_after::EndsAfter,
+    fake_start_epoch::{FakeStartEpoch, InvalidTimeScale, TimeScale},
+    family_friendly::FamilyFriendly,
+    free_of_charge::FreeOfCharge,
+    matches_search::MatchesSearch,
+    of_kinds::OfKinds,
+    on_day::OnDay,
+    recordable::Recordable,
+    sorted_by_start_time::SortedByStartTime,
+    spec::{MutatorSpec, Pipeline},
+    starts_after::StartsAfter,
     starts_before::StartsBefore,
+    suitable_for_age::SuitableForAge,
 };
 use super::event::Event;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser::Error as _};
+use std::fmt;
+
+/// What a filter does with events that lack the information it filters on (e.g. events that do
+/// not say whether they may be recorded).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unknown {
+    #[default]
+    Exclude,
+    Include,
+}
+
+impl Unknown {
+    /// Whether to keep an event, given whether it matched (or `None` if that could not be told).
+    fn keep(&self, matched: Option<bool>) -> bool {
+        matched.unwrap_or(*self == Unknown::Include)
+    }
+}
 
 pub type BoxedMutator = Box<dyn Mutator + Send + Sync>;
 
@@ -41,6 +83,107 @@ impl Mutators {
     }
 }
 
+impl fmt::Debug for Mutators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for mutator in &self.mutators {
+            match mutator.spec() {
+                Some(spec) => list.entry(&spec),
+                None => list.entry(&format_args!("<custom>")),
+            };
+        }
+        list.finish()
+    }
+}
+
+impl FromIterator<MutatorSpec> for Mutators {
+    fn from_iter<T: IntoIterator<Item = MutatorSpec>>(iter: T) -> Self {
+        Self::new(iter.into_iter().map(MutatorSpec::into_mutator).collect())
+    }
+}
+
+/// Serializes as a list of [`MutatorSpec`], failing if any custom mutators are present.
+impl Serialize for Mutators {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let specs = self
+            .mutators
+            .iter()
+            .map(|mutator| mutator.spec())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| S::Error::custom("custom mutators cannot be serialized"))?;
+
+        specs.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Mutators {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<MutatorSpec>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
 pub trait Mutator {
     fn mutate(&self, events: &mut Vec<Event>);
+
+    /// Describes the mutator, if it is one of the built-in mutators.
+    /// Custom mutators can leave this as `None`, in which case they cannot be serialized.
+    fn spec(&self) -> Option<MutatorSpec> {
+        None
+    }
+}
+
+impl Mutator for Mutators {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        Mutators::mutate(self, events);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    struct Custom;
+
+    impl Mutator for Custom {
+        fn mutate(&self, events: &mut Vec<Event>) {
+            events.clear();
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let mut mutators = Mutators::new_single(Box::<SortedByStartTime>::default());
+        mutators.push(Box::new(AtVenues::new(vec!["Stage A".to_owned()])));
+
+        let expected = json!([
+            { "type": "sorted_by_start_time" },
+            { "type": "at_venues", "venues": ["Stage A"] },
+        ]);
+
+        assert_eq!(serde_json::to_value(&mutators).unwrap(), expected);
+
+        let deserialized: Mutators = serde_json::from_value(expected).unwrap();
+        assert_eq!(format!("{deserialized:?}"), format!("{mutators:?}"));
+    }
+
+    #[test]
+    fn custom() {
+        let mut mutators = Mutators::new_single(Box::<SortedByStartTime>::default());
+        mutators.push(Box::new(Custom));
+
+        assert_eq!(
+            format!("{mutators:?}"),
+            "[SortedByStartTime(SortedByStartTime), <custom>]"
+        );
+        assert!(serde_json::to_value(&mutators).is_err());
+    }
 }
@@ -0,0 +1,79 @@
+use super::{Event, Mutator, MutatorSpec};
+use serde::{Deserialize, Serialize};
+
+/// Keeps events of any of the given kinds, named as in the upstream API (e.g. `workshop`,
+/// `youthworkshop`), ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OfKinds {
+    kinds: Vec<String>,
+}
+
+impl OfKinds {
+    pub fn new(kinds: Vec<String>) -> Self {
+        Self { kinds }
+    }
+}
+
+impl Mutator for OfKinds {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.kinds
+                .iter()
+                .any(|kind| event.kind.type_name().eq_ignore_ascii_case(kind))
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::OfKinds(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::{Kind, Workshop};
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let workshop = Workshop {
+            cost: "Free".to_owned(),
+            equiptment: None,
+            age_range: "All ages".to_owned(),
+            attendees: None,
+        };
+
+        let events = vec![
+            Event::dummy(
+                0,
+                DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+            ),
+            {
+                let mut e = Event::dummy(
+                    1,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.kind = Kind::Workshop(workshop.clone());
+                e
+            },
+            {
+                let mut e = Event::dummy(
+                    2,
+                    DateTime::parse_from_rfc3339("2024-03-12T21:00:00+00:00").unwrap(),
+                );
+                e.kind = Kind::YouthWorkshop(workshop);
+                e
+            },
+        ];
+
+        let mutator = OfKinds::new(vec!["Workshop".to_owned(), "youthworkshop".to_owned()]);
+
+        let mut mutated = events.clone();
+        mutator.mutate(&mut mutated);
+
+        assert_eq!(mutated.len(), 2);
+
+        assert_eq!(mutated[0], events[1]);
+        assert_eq!(mutated[1], events[2]);
+    }
+}
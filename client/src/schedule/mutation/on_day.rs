@@ -0,0 +1,72 @@
+use super::{Event, Mutator, MutatorSpec};
+use crate::schedule::day::{DEFAULT_ROLLOVER_HOUR, DaySelector, FestivalDay};
+use serde::{Deserialize, Serialize};
+
+/// Keeps events that start on a given festival day.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OnDay {
+    day: DaySelector,
+
+    /// Hour of the morning at which one festival day ends and the next begins.
+    #[serde(default = "default_rollover_hour")]
+    rollover_hour: u32,
+}
+
+pub(super) fn default_rollover_hour() -> u32 {
+    DEFAULT_ROLLOVER_HOUR
+}
+
+impl OnDay {
+    pub fn new(day: DaySelector, rollover_hour: u32) -> Self {
+        Self { day, rollover_hour }
+    }
+}
+
+impl Mutator for OnDay {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.day
+                .matches(FestivalDay::of(event.start, self.rollover_hour))
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::OnDay(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = [
+            "2024-05-31T22:00:00+01:00",
+            "2024-06-01T02:00:00+01:00",
+            "2024-06-01T10:00:00+01:00",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, start)| Event::dummy(id as u32, DateTime::parse_from_rfc3339(start).unwrap()))
+        .collect();
+
+        let mut mutated = events.clone();
+        OnDay::new("friday".parse().unwrap(), DEFAULT_ROLLOVER_HOUR).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone(), events[1].clone()]);
+
+        let mut mutated = events.clone();
+        OnDay::new("2024-06-01".parse().unwrap(), 0).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[1].clone(), events[2].clone()]);
+    }
+
+    #[test]
+    fn default_rollover() {
+        let mutator: OnDay = serde_json::from_str(r#"{"day": "sat"}"#).unwrap();
+        assert_eq!(
+            mutator,
+            OnDay::new("saturday".parse().unwrap(), DEFAULT_ROLLOVER_HOUR)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use super::{Event, Mutator, MutatorSpec, Unknown};
+use serde::{Deserialize, Serialize};
+
+/// Keeps events that may (or may not) be recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Recordable {
+    may_record: bool,
+
+    /// What to do with events that do not say if they may be recorded.
+    #[serde(default)]
+    unknown: Unknown,
+}
+
+impl Recordable {
+    pub fn new(may_record: bool, unknown: Unknown) -> Self {
+        Self {
+            may_record,
+            unknown,
+        }
+    }
+}
+
+impl Mutator for Recordable {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.unknown.keep(
+                event
+                    .may_record
+                    .map(|may_record| may_record == self.may_record),
+            )
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::Recordable(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = [Some(true), Some(false), None]
+            .into_iter()
+            .enumerate()
+            .map(|(id, may_record)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                e.may_record = may_record;
+                e
+            })
+            .collect();
+
+        let mut mutated = events.clone();
+        Recordable::new(true, Unknown::Exclude).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone()]);
+
+        let mut mutated = events.clone();
+        Recordable::new(false, Unknown::Include).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[1].clone(), events[2].clone()]);
+    }
+}
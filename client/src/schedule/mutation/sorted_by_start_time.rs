@@ -1,12 +1,17 @@
-use super::{Event, Mutator};
+use super::{Event, Mutator, MutatorSpec};
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SortedByStartTime {}
 
 impl Mutator for SortedByStartTime {
     fn mutate(&self, events: &mut Vec<Event>) {
         events.sort();
     }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::SortedByStartTime(self.clone()))
+    }
 }
 
 #[cfg(test)]
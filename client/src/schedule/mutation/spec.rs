@@ -0,0 +1,223 @@
+use super::{
+    AtVenues, BoxedMutator, BySpeakers, CanonicalVenues, EndsAfter, FakeStartEpoch, FamilyFriendly,
+    FreeOfCharge, MatchesSearch, Mutators, OfKinds, OnDay, Recordable, SortedByStartTime,
+    StartsAfter, StartsBefore, SuitableForAge,
+};
+use crate::schedule::query::{MatchesQuery, Query};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Description of one of the built-in mutators, allowing it to be stored in configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MutatorSpec {
+    AtVenues(AtVenues),
+    BySpeakers(BySpeakers),
+    CanonicalVenues(CanonicalVenues),
+    EndsAfter(EndsAfter),
+    FakeStartEpoch(FakeStartEpoch),
+    FamilyFriendly(FamilyFriendly),
+    FreeOfCharge(FreeOfCharge),
+    MatchesSearch(MatchesSearch),
+    OfKinds(OfKinds),
+    OnDay(OnDay),
+    Query {
+        query: Query,
+
+        /// Hour of the morning at which one festival day ends and the next begins.
+        #[serde(default = "super::on_day::default_rollover_hour")]
+        rollover_hour: u32,
+    },
+    Recordable(Recordable),
+    SortedByStartTime(SortedByStartTime),
+    StartsAfter(StartsAfter),
+    StartsBefore(StartsBefore),
+    SuitableForAge(SuitableForAge),
+}
+
+impl MutatorSpec {
+    /// Creates the mutator this describes.
+    pub fn into_mutator(self) -> BoxedMutator {
+        match self {
+            MutatorSpec::AtVenues(m) => Box::new(m),
+            MutatorSpec::BySpeakers(m) => Box::new(m),
+            MutatorSpec::CanonicalVenues(m) => Box::new(m),
+            MutatorSpec::EndsAfter(m) => Box::new(m),
+            MutatorSpec::FakeStartEpoch(m) => Box::new(m),
+            MutatorSpec::FamilyFriendly(m) => Box::new(m),
+            MutatorSpec::FreeOfCharge(m) => Box::new(m),
+            MutatorSpec::MatchesSearch(m) => Box::new(m),
+            MutatorSpec::OfKinds(m) => Box::new(m),
+            MutatorSpec::OnDay(m) => Box::new(m),
+            MutatorSpec::Query {
+                query,
+                rollover_hour,
+            } => Box::new(MatchesQuery::new(query, rollover_hour)),
+            MutatorSpec::Recordable(m) => Box::new(m),
+            MutatorSpec::SortedByStartTime(m) => Box::new(m),
+            MutatorSpec::StartsAfter(m) => Box::new(m),
+            MutatorSpec::StartsBefore(m) => Box::new(m),
+            MutatorSpec::SuitableForAge(m) => Box::new(m),
+        }
+    }
+}
+
+/// A pipeline of built-in mutators, as stored in a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Pipeline {
+    pub mutators: Vec<MutatorSpec>,
+}
+
+impl Pipeline {
+    /// Loads a pipeline from a TOML file (if the file has a `.toml` extension) or a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        crate::config::load_config(path.as_ref())
+    }
+}
+
+impl From<Pipeline> for Mutators {
+    fn from(pipeline: Pipeline) -> Self {
+        pipeline.mutators.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::{day::DEFAULT_ROLLOVER_HOUR, event::Event, mutation::Unknown};
+    use chrono::DateTime;
+    use serde_json::json;
+
+    fn pipeline() -> Pipeline {
+        Pipeline {
+            mutators: vec![
+                MutatorSpec::SortedByStartTime(SortedByStartTime::default()),
+                MutatorSpec::FakeStartEpoch(FakeStartEpoch::new(
+                    DateTime::parse_from_rfc3339("2024-04-01T17:00:00+01:00").unwrap(),
+                )),
+                MutatorSpec::AtVenues(AtVenues::new(vec!["Stage A".to_owned()])),
+                MutatorSpec::Query {
+                    query: Query::parse("kind:workshop start>=sat10:00").unwrap(),
+                    rollover_hour: 6,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn json() {
+        let expected = json!({
+            "mutators": [
+                { "type": "sorted_by_start_time" },
+                { "type": "fake_start_epoch", "epoch": "2024-04-01T17:00:00+01:00" },
+                { "type": "at_venues", "venues": ["Stage A"] },
+                { "type": "query", "query": "kind:workshop start>=sat10:00", "rollover_hour": 6 },
+            ]
+        });
+
+        assert_eq!(serde_json::to_value(pipeline()).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<Pipeline>(expected).unwrap(),
+            pipeline()
+        );
+    }
+
+    #[test]
+    fn toml() {
+        let data = r#"
+            [[mutators]]
+            type = "sorted_by_start_time"
+
+            [[mutators]]
+            type = "fake_start_epoch"
+            epoch = "2024-04-01T17:00:00+01:00"
+
+            [[mutators]]
+            type = "at_venues"
+            venues = ["Stage A"]
+
+            [[mutators]]
+            type = "query"
+            query = "kind:workshop start>=sat10:00"
+            rollover_hour = 6
+        "#;
+
+        assert_eq!(toml::from_str::<Pipeline>(data).unwrap(), pipeline());
+    }
+
+    #[test]
+    fn unknown_defaults_to_exclude() {
+        let spec: MutatorSpec = serde_json::from_value(json!({
+            "type": "family_friendly",
+            "family_friendly": true,
+        }))
+        .unwrap();
+        assert_eq!(
+            spec,
+            MutatorSpec::FamilyFriendly(FamilyFriendly::new(true, Unknown::Exclude))
+        );
+
+        let spec: MutatorSpec = serde_json::from_value(json!({
+            "type": "recordable",
+            "may_record": true,
+            "unknown": "include",
+        }))
+        .unwrap();
+        assert_eq!(
+            spec,
+            MutatorSpec::Recordable(Recordable::new(true, Unknown::Include))
+        );
+    }
+
+    #[test]
+    fn query_rollover_hour() {
+        let spec: MutatorSpec = serde_json::from_value(json!({
+            "type": "query",
+            "query": "day:fri",
+        }))
+        .unwrap();
+        assert_eq!(
+            spec,
+            MutatorSpec::Query {
+                query: Query::parse("day:fri").unwrap(),
+                rollover_hour: DEFAULT_ROLLOVER_HOUR,
+            }
+        );
+
+        let mut events = vec![Event::dummy(
+            0,
+            DateTime::parse_from_rfc3339("2024-06-01T05:00:00+01:00").unwrap(),
+        )];
+        spec.into_mutator().mutate(&mut events);
+        assert!(events.is_empty());
+
+        let spec = MutatorSpec::Query {
+            query: Query::parse("day:fri").unwrap(),
+            rollover_hour: 6,
+        };
+        let mut events = vec![Event::dummy(
+            0,
+            DateTime::parse_from_rfc3339("2024-06-01T05:00:00+01:00").unwrap(),
+        )];
+        spec.into_mutator().mutate(&mut events);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn invalid_query() {
+        let result = serde_json::from_value::<Pipeline>(json!({
+            "mutators": [{ "type": "query", "query": "colour:red" }]
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_file() {
+        let path = std::env::temp_dir().join("emfcamp-schedule-api-pipeline-test.json");
+        std::fs::write(&path, serde_json::to_string(&pipeline()).unwrap()).unwrap();
+
+        assert_eq!(Pipeline::from_file(&path).unwrap(), pipeline());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
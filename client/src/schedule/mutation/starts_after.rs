@@ -1,6 +1,8 @@
-use super::{Event, Mutator};
+use super::{Event, Mutator, MutatorSpec};
 use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct StartsAfter {
     timestamp: DateTime<FixedOffset>,
 }
@@ -15,6 +17,10 @@ impl Mutator for StartsAfter {
     fn mutate(&self, events: &mut Vec<Event>) {
         events.retain(|event| event.start > self.timestamp);
     }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::StartsAfter(self.clone()))
+    }
 }
 
 #[cfg(test)]
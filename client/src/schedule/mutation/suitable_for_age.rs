@@ -0,0 +1,76 @@
+use super::{Event, Mutator, MutatorSpec, Unknown};
+use serde::{Deserialize, Serialize};
+
+/// Keeps workshops that someone of the given age can attend, see [`Workshop::suitable_for_age`].
+///
+/// [`Workshop::suitable_for_age`]: crate::schedule::event::Workshop::suitable_for_age
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SuitableForAge {
+    age: u32,
+
+    /// What to do with events that are not workshops or have an age range that cannot be
+    /// understood.
+    #[serde(default)]
+    unknown: Unknown,
+}
+
+impl SuitableForAge {
+    pub fn new(age: u32, unknown: Unknown) -> Self {
+        Self { age, unknown }
+    }
+}
+
+impl Mutator for SuitableForAge {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        events.retain(|event| {
+            self.unknown.keep(
+                event
+                    .kind
+                    .workshop()
+                    .and_then(|workshop| workshop.suitable_for_age(self.age)),
+            )
+        });
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::SuitableForAge(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::{Kind, Workshop};
+    use chrono::DateTime;
+
+    #[test]
+    fn basic() {
+        let events: Vec<_> = [Some("8+"), Some("12-16"), Some("Ask"), None]
+            .into_iter()
+            .enumerate()
+            .map(|(id, age_range)| {
+                let mut e = Event::dummy(
+                    id as u32,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                );
+                if let Some(age_range) = age_range {
+                    e.kind = Kind::YouthWorkshop(Workshop {
+                        cost: "".to_owned(),
+                        equiptment: None,
+                        age_range: age_range.to_owned(),
+                        attendees: None,
+                    });
+                }
+                e
+            })
+            .collect();
+
+        let mut mutated = events.clone();
+        SuitableForAge::new(10, Unknown::Exclude).mutate(&mut mutated);
+        assert_eq!(mutated, vec![events[0].clone()]);
+
+        let mut mutated = events.clone();
+        SuitableForAge::new(14, Unknown::Include).mutate(&mut mutated);
+        assert_eq!(mutated, events.to_vec());
+    }
+}
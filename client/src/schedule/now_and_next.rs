@@ -1,67 +1,326 @@
-use super::event::{Event, RelativeTime};
-use chrono::{DateTime, FixedOffset};
+use super::{
+    day::{DEFAULT_ROLLOVER_HOUR, FestivalDay},
+    event::{Event, RelativeTime},
+    index::ScheduleIndex,
+    venue::{VenueInfo, VenueRegistry},
+};
+use chrono::{DateTime, Duration, FixedOffset};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_with::{DurationSeconds, serde_as};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct NowAndNext {
     pub now: DateTime<FixedOffset>,
-    pub guide: HashMap<String, VenueNowAndNext>,
+
+    /// Each venue, in the order asked for (see [`VenueOrder`]).
+    pub guide: Vec<VenueGuide>,
 }
 
 impl NowAndNext {
     pub(super) fn new(events: &[Event], now: DateTime<FixedOffset>) -> Self {
-        let mut result = Self {
-            now,
-            guide: Default::default(),
-        };
+        NowAndNextBuilder::new(events, now).build()
+    }
 
-        for venue in super::get_unique_venues_from_events(events) {
-            let events_now = events
-                .iter()
-                .filter_map(|e| {
-                    if e.venue == venue && e.relative_to(now) == RelativeTime::Now {
-                        Some(e.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+    /// What is on at a venue.
+    pub fn venue(&self, name: &str) -> Option<&VenueNowAndNext> {
+        self.guide
+            .iter()
+            .find(|guide| guide.venue == name)
+            .map(|guide| &guide.now_and_next)
+    }
+}
+
+/// How the venues in a [`NowAndNext`] are ordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VenueOrder {
+    /// By name.
+    #[default]
+    Alphabetical,
+
+    /// Venues given a priority first, in that order, then in the order of the venue registry,
+    /// then by name (see [`NowAndNextBuilder::priorities`] and
+    /// [`NowAndNextBuilder::venue_registry`]).
+    Priority,
+
+    /// By the start time of their next events, with venues that have nothing coming up last.
+    Soonest,
+}
+
+impl FromStr for VenueOrder {
+    type Err = InvalidVenueOrder;
 
-            // Find the next event to happen in the future.
-            // This will determine the start time of the next event(s).
-            let events_next = match events
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "alphabetical" => Ok(Self::Alphabetical),
+            "priority" => Ok(Self::Priority),
+            "soonest" => Ok(Self::Soonest),
+            _ => Err(InvalidVenueOrder(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for VenueOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Alphabetical => write!(f, "alphabetical"),
+            Self::Priority => write!(f, "priority"),
+            Self::Soonest => write!(f, "soonest"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid venue order `{0}`, expected one of alphabetical, priority or soonest")]
+pub struct InvalidVenueOrder(String);
+
+/// Builds a [`NowAndNext`] that also looks further ahead than the next event at each venue.
+///
+/// By default nothing is listed as `later`. When more than one limit is given, events are only
+/// listed as `later` if they are within all of them.
+#[derive(Debug, Clone)]
+pub struct NowAndNextBuilder<'a> {
+    source: Source<'a>,
+    now: DateTime<FixedOffset>,
+    slots: Option<usize>,
+    event_count: Option<usize>,
+    horizon: Option<Duration>,
+    rollover_hour: u32,
+    order: VenueOrder,
+    priorities: Vec<String>,
+    registry: Option<&'a VenueRegistry>,
+}
+
+/// Where the events come from.
+#[derive(Debug, Clone, Copy)]
+enum Source<'a> {
+    /// Events in no particular order, which are scanned through for each venue.
+    Events(&'a [Event]),
+
+    Index(&'a ScheduleIndex),
+}
+
+impl<'a> Source<'a> {
+    fn venues(&self) -> Vec<String> {
+        match self {
+            Source::Events(events) => super::get_unique_venues_from_events(events),
+            Source::Index(index) => index.venues().map(str::to_owned).collect(),
+        }
+    }
+
+    fn now(&self, venue: &str, now: DateTime<FixedOffset>) -> Vec<Event> {
+        match self {
+            Source::Events(events) => events
                 .iter()
-                .find(|e| e.venue == venue && e.relative_to(now) == RelativeTime::Future)
-            {
-                // Then find all events that have the same start time.
-                // This set of events are the next to happen at the venue in question.
-                Some(first_future_event) => events
+                .filter(|e| e.venue == venue && e.relative_to(now) == RelativeTime::Now)
+                .cloned()
+                .collect(),
+            Source::Index(index) => index.on_at_venue(venue, now).into_iter().cloned().collect(),
+        }
+    }
+
+    /// Events at a venue that start after now, in order of start time.
+    fn future(&self, venue: &str, now: DateTime<FixedOffset>) -> Vec<&'a Event> {
+        match self {
+            Source::Events(events) => {
+                let mut future: Vec<_> = events
                     .iter()
-                    .filter_map(|e| {
-                        if e.venue == venue && e.start == first_future_event.start {
-                            Some(e.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect(),
-                None => Vec::default(),
-            };
-
-            result.guide.insert(
-                venue.clone(),
-                VenueNowAndNext {
+                    .filter(|e| e.venue == venue && e.relative_to(now) == RelativeTime::Future)
+                    .collect();
+                future.sort_by_key(|e| e.start);
+                future
+            }
+            Source::Index(index) => index.upcoming_at(venue, now).collect(),
+        }
+    }
+}
+
+impl<'a> NowAndNextBuilder<'a> {
+    pub(super) fn new(events: &'a [Event], now: DateTime<FixedOffset>) -> Self {
+        Self::with_source(Source::Events(events), now)
+    }
+
+    pub(super) fn from_index(index: &'a ScheduleIndex, now: DateTime<FixedOffset>) -> Self {
+        Self::with_source(Source::Index(index), now)
+    }
+
+    fn with_source(source: Source<'a>, now: DateTime<FixedOffset>) -> Self {
+        Self {
+            source,
+            now,
+            slots: None,
+            event_count: None,
+            horizon: None,
+            rollover_hour: DEFAULT_ROLLOVER_HOUR,
+            order: VenueOrder::default(),
+            priorities: Vec::new(),
+            registry: None,
+        }
+    }
+
+    /// List this many start times (including that of the next events) at each venue.
+    pub fn slots(mut self, slots: usize) -> Self {
+        self.slots = Some(slots);
+        self
+    }
+
+    /// List this many upcoming events (including the next events) at each venue.
+    pub fn events(mut self, count: usize) -> Self {
+        self.event_count = Some(count);
+        self
+    }
+
+    /// List the events that start within this long of now.
+    pub fn horizon(mut self, horizon: Duration) -> Self {
+        self.horizon = Some(horizon);
+        self
+    }
+
+    /// Hour of the morning at which one festival day ends and the next begins, used to tell if a
+    /// venue has finished for the day.
+    pub fn rollover_hour(mut self, rollover_hour: u32) -> Self {
+        self.rollover_hour = rollover_hour;
+        self
+    }
+
+    pub fn order(mut self, order: VenueOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Venues to list first (in this order) when ordering by [`VenueOrder::Priority`].
+    pub fn priorities(mut self, venues: Vec<String>) -> Self {
+        self.priorities = venues;
+        self
+    }
+
+    /// Known venues, used to order venues by [`VenueOrder::Priority`] and to include the details
+    /// of each venue.
+    pub fn venue_registry(mut self, registry: &'a VenueRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    fn looks_ahead(&self) -> bool {
+        self.slots.is_some() || self.event_count.is_some() || self.horizon.is_some()
+    }
+
+    pub fn build(self) -> NowAndNext {
+        let now = self.now;
+
+        let mut guide = Vec::new();
+
+        let today = FestivalDay::of(now, self.rollover_hour);
+
+        for venue in self.source.venues() {
+            let events_now = self.source.now(&venue, now);
+
+            // Group future events by start time, the first of which are the next to happen at the
+            // venue in question.
+            let future = self.source.future(&venue, now);
+            let mut slots = future.chunk_by(|a, b| a.start == b.start);
+
+            let events_next: Vec<_> = slots
+                .next()
+                .map(|slot| slot.iter().map(|&e| e.clone()).collect())
+                .unwrap_or_default();
+
+            let mut events_later = Vec::new();
+            if self.looks_ahead() {
+                let slot_limit = self.slots.map(|n| n.saturating_sub(1));
+                let event_limit = self
+                    .event_count
+                    .map(|n| n.saturating_sub(events_next.len()));
+                let horizon = self
+                    .horizon
+                    .and_then(|horizon| now.checked_add_signed(horizon));
+
+                for (i, slot) in slots.enumerate() {
+                    if slot_limit.is_some_and(|limit| i >= limit)
+                        || horizon.is_some_and(|horizon| slot[0].start >= horizon)
+                        || event_limit.is_some_and(|limit| events_later.len() >= limit)
+                    {
+                        break;
+                    }
+                    events_later.extend(slot.iter().map(|&e| e.clone()));
+                }
+
+                if let Some(limit) = event_limit {
+                    events_later.truncate(limit);
+                }
+            }
+
+            let busy_until = events_now.iter().map(|e| e.end).max();
+            let next_start = events_next.first().map(|e| e.start);
+
+            guide.push(VenueGuide {
+                info: self.registry.and_then(|r| r.find(&venue)).cloned(),
+                venue,
+                now_and_next: VenueNowAndNext {
+                    remaining: busy_until.map(|end| end - now),
+                    starts_in: next_start.map(|start| start - now),
+                    gap: next_start
+                        .map(|start| (start - busy_until.unwrap_or(now)).max(Duration::zero())),
+                    finished_for_day: events_now.is_empty()
+                        && next_start.is_none_or(|start| {
+                            FestivalDay::of(start, self.rollover_hour) != today
+                        }),
                     now: events_now,
                     next: events_next,
+                    later: events_later,
                 },
-            );
+            });
         }
 
-        result
+        self.sort(&mut guide);
+
+        NowAndNext { now, guide }
+    }
+
+    fn sort(&self, guide: &mut [VenueGuide]) {
+        guide.sort_by(|a, b| a.venue.cmp(&b.venue));
+
+        match self.order {
+            VenueOrder::Alphabetical => {}
+            VenueOrder::Priority => {
+                let rank = |venue: &str| {
+                    self.priorities
+                        .iter()
+                        .position(|p| p.to_lowercase() == venue.to_lowercase())
+                };
+                guide.sort_by(|a, b| match (rank(&a.venue), rank(&b.venue)) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => self
+                        .registry
+                        .map_or(Ordering::Equal, |r| r.compare(&a.venue, &b.venue)),
+                });
+            }
+            VenueOrder::Soonest => {
+                guide.sort_by_key(|g| {
+                    let start = g.now_and_next.next.first().map(|e| e.start);
+                    (start.is_none(), start)
+                });
+            }
+        }
     }
 }
 
+/// What is on at a venue, along with the details of the venue if it is known.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VenueGuide {
+    pub venue: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<VenueInfo>,
+
+    #[serde(flatten)]
+    pub now_and_next: VenueNowAndNext,
+}
+
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct VenueNowAndNext {
     /// An event is "now" if the query time is between its start and end timestamps.
@@ -70,6 +329,31 @@ pub struct VenueNowAndNext {
     /// An event is "next" if is the first event in chronological order to have a start timestamp
     /// that is later than the query timestamp.
     pub next: Vec<Event>,
+
+    /// Events after those that are "next", as far ahead as was asked for (see
+    /// [`NowAndNextBuilder`]), in chronological order.
+    #[serde(default)]
+    pub later: Vec<Event>,
+
+    /// Time until all of the "now" events have ended, if there are any.
+    #[serde_as(as = "Option<DurationSeconds<i64>>")]
+    #[serde(default, rename = "remaining_seconds")]
+    pub remaining: Option<Duration>,
+
+    /// Time until the "next" events start, if there are any.
+    #[serde_as(as = "Option<DurationSeconds<i64>>")]
+    #[serde(default, rename = "starts_in_seconds")]
+    pub starts_in: Option<Duration>,
+
+    /// Time the venue is empty for before the "next" events start, i.e. from the end of the "now"
+    /// events (or from now, if nothing is on) until then. Zero if the events run back to back.
+    #[serde_as(as = "Option<DurationSeconds<i64>>")]
+    #[serde(default, rename = "gap_seconds")]
+    pub gap: Option<Duration>,
+
+    /// True if nothing is on now and nothing else starts at the venue on the current festival day.
+    #[serde(default)]
+    pub finished_for_day: bool,
 }
 
 #[cfg(test)]
@@ -113,11 +397,20 @@ mod test {
 
         assert_eq!(now_and_next.guide.len(), 2);
 
-        assert_eq!(now_and_next.guide["venue 1"].now, vec![events[0].clone()]);
-        assert_eq!(now_and_next.guide["venue 1"].next, vec![events[2].clone()]);
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().now,
+            vec![events[0].clone()]
+        );
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().next,
+            vec![events[2].clone()]
+        );
 
-        assert_eq!(now_and_next.guide["venue 2"].now, vec![events[1].clone()]);
-        assert_eq!(now_and_next.guide["venue 2"].next, Vec::default());
+        assert_eq!(
+            now_and_next.venue("venue 2").unwrap().now,
+            vec![events[1].clone()]
+        );
+        assert_eq!(now_and_next.venue("venue 2").unwrap().next, Vec::default());
     }
 
     #[test]
@@ -156,11 +449,17 @@ mod test {
 
         assert_eq!(now_and_next.guide.len(), 2);
 
-        assert_eq!(now_and_next.guide["venue 1"].now, Vec::default());
-        assert_eq!(now_and_next.guide["venue 1"].next, vec![events[0].clone()]);
+        assert_eq!(now_and_next.venue("venue 1").unwrap().now, Vec::default());
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().next,
+            vec![events[0].clone()]
+        );
 
-        assert_eq!(now_and_next.guide["venue 2"].now, Vec::default());
-        assert_eq!(now_and_next.guide["venue 2"].next, vec![events[1].clone()]);
+        assert_eq!(now_and_next.venue("venue 2").unwrap().now, Vec::default());
+        assert_eq!(
+            now_and_next.venue("venue 2").unwrap().next,
+            vec![events[1].clone()]
+        );
     }
 
     #[test]
@@ -208,10 +507,13 @@ mod test {
         assert_eq!(now_and_next.guide.len(), 2);
 
         assert_eq!(
-            now_and_next.guide["venue 1"].now,
+            now_and_next.venue("venue 1").unwrap().now,
             vec![events[0].clone(), events[2].clone()]
         );
-        assert_eq!(now_and_next.guide["venue 1"].next, vec![events[3].clone()]);
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().next,
+            vec![events[3].clone()]
+        );
     }
 
     #[test]
@@ -258,10 +560,269 @@ mod test {
 
         assert_eq!(now_and_next.guide.len(), 2);
 
-        assert_eq!(now_and_next.guide["venue 1"].now, Vec::default(),);
+        assert_eq!(now_and_next.venue("venue 1").unwrap().now, Vec::default(),);
         assert_eq!(
-            now_and_next.guide["venue 1"].next,
+            now_and_next.venue("venue 1").unwrap().next,
             vec![events[0].clone(), events[2].clone()]
         );
     }
+
+    fn lookahead_events() -> Vec<Event> {
+        let event = |id, start: &str, venue: &str| {
+            let mut e = Event::dummy(id, DateTime::parse_from_rfc3339(start).unwrap());
+            e.venue = venue.to_owned();
+            e
+        };
+
+        vec![
+            event(0, "2024-03-12T20:00:00+00:00", "venue 1"),
+            event(1, "2024-03-12T21:00:00+00:00", "venue 1"),
+            event(2, "2024-03-12T22:00:00+00:00", "venue 1"),
+            event(3, "2024-03-12T22:00:00+00:00", "venue 1"),
+            event(4, "2024-03-12T23:00:00+00:00", "venue 1"),
+            event(5, "2024-03-13T10:00:00+00:00", "venue 1"),
+            event(6, "2024-03-12T20:00:00+00:00", "venue 2"),
+        ]
+    }
+
+    fn later_ids(now_and_next: &NowAndNext, venue: &str) -> Vec<u32> {
+        now_and_next
+            .venue(venue)
+            .unwrap()
+            .later
+            .iter()
+            .map(|e| e.id)
+            .collect()
+    }
+
+    #[test]
+    fn no_lookahead() {
+        let events = lookahead_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNext::new(&events, t);
+
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().next,
+            vec![events[1].clone()]
+        );
+        assert_eq!(later_ids(&now_and_next, "venue 1"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn lookahead_slots() {
+        let events = lookahead_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNextBuilder::new(&events, t).slots(3).build();
+
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().now,
+            vec![events[0].clone()]
+        );
+        assert_eq!(
+            now_and_next.venue("venue 1").unwrap().next,
+            vec![events[1].clone()]
+        );
+        assert_eq!(later_ids(&now_and_next, "venue 1"), vec![2, 3, 4]);
+        assert_eq!(later_ids(&now_and_next, "venue 2"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn lookahead_events_count() {
+        let events = lookahead_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNextBuilder::new(&events, t).events(3).build();
+
+        assert_eq!(later_ids(&now_and_next, "venue 1"), vec![2, 3]);
+    }
+
+    #[test]
+    fn lookahead_horizon() {
+        let events = lookahead_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+
+        let now_and_next = NowAndNextBuilder::new(&events, t)
+            .horizon(Duration::try_hours(3).unwrap())
+            .build();
+        assert_eq!(later_ids(&now_and_next, "venue 1"), vec![2, 3, 4]);
+
+        let now_and_next = NowAndNextBuilder::new(&events, t)
+            .horizon(Duration::try_hours(3).unwrap())
+            .events(2)
+            .build();
+        assert_eq!(later_ids(&now_and_next, "venue 1"), vec![2]);
+    }
+
+    #[test]
+    fn countdowns() {
+        let events = lookahead_events();
+        let minutes = |m| Some(Duration::try_minutes(m).unwrap());
+
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNext::new(&events, t);
+
+        let venue = now_and_next.venue("venue 1").unwrap();
+        assert_eq!(venue.remaining, minutes(30));
+        assert_eq!(venue.starts_in, minutes(30));
+        assert_eq!(venue.gap, minutes(0));
+        assert!(!venue.finished_for_day);
+
+        let venue = now_and_next.venue("venue 2").unwrap();
+        assert_eq!(venue.remaining, minutes(30));
+        assert_eq!(venue.starts_in, None);
+        assert_eq!(venue.gap, None);
+        assert!(!venue.finished_for_day);
+    }
+
+    #[test]
+    fn idle_venues() {
+        let events = lookahead_events();
+        let minutes = |m| Some(Duration::try_minutes(m).unwrap());
+
+        // Between the last event of the night and the first of the next day
+        let t = DateTime::parse_from_rfc3339("2024-03-13T00:30:00+00:00").unwrap();
+        let now_and_next = NowAndNext::new(&events, t);
+
+        let venue = now_and_next.venue("venue 1").unwrap();
+        assert_eq!(venue.remaining, None);
+        assert_eq!(venue.starts_in, minutes(570));
+        assert_eq!(venue.gap, minutes(570));
+        assert!(venue.finished_for_day);
+
+        // Still the same festival day as the next event with an earlier rollover
+        let now_and_next = NowAndNextBuilder::new(&events, t).rollover_hour(0).build();
+        assert!(!now_and_next.venue("venue 1").unwrap().finished_for_day);
+
+        assert!(now_and_next.venue("venue 2").unwrap().finished_for_day);
+    }
+
+    #[test]
+    fn serialize_countdowns() {
+        let events = lookahead_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNext::new(&events, t);
+
+        let venue = serde_json::to_value(now_and_next.venue("venue 2").unwrap()).unwrap();
+        assert_eq!(venue["remaining_seconds"], 1800);
+        assert_eq!(venue["starts_in_seconds"], serde_json::Value::Null);
+        assert_eq!(venue["gap_seconds"], serde_json::Value::Null);
+        assert_eq!(venue["finished_for_day"], false);
+    }
+
+    fn venue_names(now_and_next: &NowAndNext) -> Vec<&str> {
+        now_and_next
+            .guide
+            .iter()
+            .map(|g| g.venue.as_str())
+            .collect()
+    }
+
+    fn ordering_events() -> Vec<Event> {
+        let event = |id, start: &str, venue: &str| {
+            let mut e = Event::dummy(id, DateTime::parse_from_rfc3339(start).unwrap());
+            e.venue = venue.to_owned();
+            e
+        };
+
+        vec![
+            event(0, "2024-03-12T22:00:00+00:00", "Bar"),
+            event(1, "2024-03-12T21:00:00+00:00", "Stage B"),
+            event(2, "2024-03-12T21:30:00+00:00", "Stage A"),
+            event(3, "2024-03-12T19:00:00+00:00", "Lounge"),
+        ]
+    }
+
+    #[test]
+    fn order_alphabetical() {
+        let events = ordering_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+
+        for _ in 0..5 {
+            let now_and_next = NowAndNext::new(&events, t);
+            assert_eq!(
+                venue_names(&now_and_next),
+                vec!["Bar", "Lounge", "Stage A", "Stage B"]
+            );
+        }
+    }
+
+    #[test]
+    fn order_soonest() {
+        let events = ordering_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNextBuilder::new(&events, t)
+            .order(VenueOrder::Soonest)
+            .build();
+
+        assert_eq!(
+            venue_names(&now_and_next),
+            vec!["Stage B", "Stage A", "Bar", "Lounge"]
+        );
+    }
+
+    #[test]
+    fn order_priority() {
+        let registry: VenueRegistry = toml::from_str(
+            r#"
+            [[venues]]
+            id = "stage-a"
+            name = "Stage A"
+            order = 1
+
+            [[venues]]
+            id = "stage-b"
+            name = "Stage B"
+            order = 2
+            "#,
+        )
+        .unwrap();
+
+        let events = ordering_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+
+        let now_and_next = NowAndNextBuilder::new(&events, t)
+            .order(VenueOrder::Priority)
+            .venue_registry(&registry)
+            .build();
+        assert_eq!(
+            venue_names(&now_and_next),
+            vec!["Stage A", "Stage B", "Bar", "Lounge"]
+        );
+        assert_eq!(now_and_next.guide[0].info.as_ref().unwrap().id, "stage-a");
+        assert_eq!(now_and_next.guide[2].info, None);
+
+        let now_and_next = NowAndNextBuilder::new(&events, t)
+            .order(VenueOrder::Priority)
+            .priorities(vec!["lounge".to_owned(), "Stage B".to_owned()])
+            .venue_registry(&registry)
+            .build();
+        assert_eq!(
+            venue_names(&now_and_next),
+            vec!["Lounge", "Stage B", "Stage A", "Bar"]
+        );
+    }
+
+    #[test]
+    fn parse_order() {
+        assert_eq!("Soonest".parse(), Ok(VenueOrder::Soonest));
+        assert_eq!(VenueOrder::Priority.to_string(), "priority");
+        assert!("random".parse::<VenueOrder>().is_err());
+    }
+
+    #[test]
+    fn serialize_guide() {
+        let events = ordering_events();
+        let t = DateTime::parse_from_rfc3339("2024-03-12T20:30:00+00:00").unwrap();
+        let now_and_next = NowAndNext::new(&events, t);
+
+        let json = serde_json::to_value(&now_and_next).unwrap();
+        assert_eq!(json["guide"][0]["venue"], "Bar");
+        assert_eq!(json["guide"][0]["next"][0]["id"], 0);
+        assert_eq!(json["guide"][0]["starts_in_seconds"], 5400);
+
+        let decoded: NowAndNext = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            decoded.venue("Bar").unwrap().starts_in,
+            Some(Duration::try_minutes(90).unwrap())
+        );
+    }
 }
@@ -0,0 +1,520 @@
+mod parser;
+mod time;
+
+use self::time::ScheduleDates;
+pub use self::{parser::ParseError, time::TimeSpec};
+use super::{
+    day::{DaySelector, FestivalDay},
+    event::Event,
+    mutation::{
+        AtVenues, BoxedMutator, EndsAfter, FamilyFriendly, FreeOfCharge, Mutator, MutatorSpec,
+        Mutators, OfKinds, OnDay, Recordable, StartsAfter, StartsBefore, SuitableForAge, Unknown,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// A filter over the schedule, written as a space separated list of terms that must all match.
+///
+/// - `venue:"Stage A"` - events at a venue (repeating this matches any of the venues)
+/// - `kind:workshop` (or `type:`) - events of a kind (repeating this matches any of the kinds)
+/// - `id:42` - a specific event (repeating this matches any of the events)
+/// - `title:rust`, `speaker:dan` - case insensitive substring of the title or speaker
+/// - `family_friendly`, `may_record` - events that are explicitly marked as such
+/// - `free` - workshops that are free to attend
+/// - `age:10` - workshops that someone of the given age can attend
+/// - `day:sat`, `day:2024-06-01` - events starting on a festival day, where events in the early
+///   hours of the morning count as part of the previous day (see [`MatchesQuery`])
+/// - `start>=sat10:00`, `end<2024-06-01T18:00:00+01:00` - events starting or ending relative to a
+///   time, using any of `<`, `<=`, `>` or `>=`
+///
+/// Any term can be negated by prefixing it with `-`, e.g. `-kind:talk`.
+///
+/// Times may be given as an RFC 3339 timestamp or as a day of the week and time (e.g. `sat10:00`,
+/// `"saturday 10:00"`), the latter refers to the first day in the schedule that falls on that day
+/// of the week, in the timezone of the events on that day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Venue(String),
+    Kind(String),
+    Id(u32),
+    Title(String),
+    Speaker(String),
+    FamilyFriendly,
+    MayRecord,
+    Free,
+    Age(u32),
+    Day(DaySelector),
+    Start(Comparison, TimeSpec),
+    End(Comparison, TimeSpec),
+    Not(Box<Term>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Before,
+    BeforeOrAt,
+    After,
+    AtOrAfter,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            terms: parser::parse(input)?,
+        })
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Query {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Query> for String {
+    fn from(query: Query) -> Self {
+        query.to_string()
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{term}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Venue(s) => write!(f, "venue:{}", Quoted(s)),
+            Term::Kind(s) => write!(f, "kind:{}", Quoted(s)),
+            Term::Id(id) => write!(f, "id:{id}"),
+            Term::Title(s) => write!(f, "title:{}", Quoted(s)),
+            Term::Speaker(s) => write!(f, "speaker:{}", Quoted(s)),
+            Term::FamilyFriendly => write!(f, "family_friendly"),
+            Term::MayRecord => write!(f, "may_record"),
+            Term::Free => write!(f, "free"),
+            Term::Age(age) => write!(f, "age:{age}"),
+            Term::Day(day) => write!(f, "day:{day}"),
+            Term::Start(comparison, time) => write!(f, "start{comparison}{time}"),
+            Term::End(comparison, time) => write!(f, "end{comparison}{time}"),
+            Term::Not(term) => write!(f, "-{term}"),
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Comparison::Before => "<",
+            Comparison::BeforeOrAt => "<=",
+            Comparison::After => ">",
+            Comparison::AtOrAfter => ">=",
+        })
+    }
+}
+
+/// Formats a value, quoting it only if needed.
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.is_empty() && !self.0.contains(|c: char| c.is_whitespace() || c == '"') {
+            return f.write_str(self.0);
+        }
+
+        write!(f, "\"")?;
+        for c in self.0.chars() {
+            if c == '"' || c == '\\' {
+                write!(f, "\\")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, "\"")
+    }
+}
+
+/// Keeps events that match a [`Query`], with `day:` terms ending each festival day at the given
+/// hour of the morning (so that they select the same events as [`OnDay`] with the same hour).
+///
+/// The query is turned into the equivalent mutators once, when this is created.
+#[derive(Debug)]
+pub struct MatchesQuery {
+    query: Query,
+    rollover_hour: u32,
+    mutators: Mutators,
+}
+
+impl MatchesQuery {
+    pub fn new(query: Query, rollover_hour: u32) -> Self {
+        let mutators = query.to_mutators(rollover_hour);
+        Self {
+            query,
+            rollover_hour,
+            mutators,
+        }
+    }
+}
+
+impl Mutator for MatchesQuery {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        self.mutators.mutate(events);
+    }
+
+    fn spec(&self) -> Option<MutatorSpec> {
+        Some(MutatorSpec::Query {
+            query: self.query.clone(),
+            rollover_hour: self.rollover_hour,
+        })
+    }
+}
+
+impl Query {
+    fn to_mutators(&self, rollover_hour: u32) -> Mutators {
+        let mut matches = Vec::new();
+        let mut kinds = Vec::new();
+        let mut ids = Vec::new();
+        let mut venues = Vec::new();
+        let mut others: Vec<BoxedMutator> = Vec::new();
+
+        for term in self.terms.iter().cloned() {
+            match term {
+                Term::Venue(venue) => venues.push(venue),
+                Term::Kind(kind) => kinds.push(kind),
+                Term::Id(_) => ids.push(term),
+                Term::Start(Comparison::After, TimeSpec::Absolute(t)) => {
+                    others.push(Box::new(StartsAfter::new(t)));
+                }
+                Term::Start(Comparison::Before, TimeSpec::Absolute(t)) => {
+                    others.push(Box::new(StartsBefore::new(t)));
+                }
+                Term::End(Comparison::After, TimeSpec::Absolute(t)) => {
+                    others.push(Box::new(EndsAfter::new(t)));
+                }
+                Term::FamilyFriendly => {
+                    others.push(Box::new(FamilyFriendly::new(true, Unknown::Exclude)));
+                }
+                Term::MayRecord => others.push(Box::new(Recordable::new(true, Unknown::Exclude))),
+                Term::Free => others.push(Box::new(FreeOfCharge::new(true, Unknown::Exclude))),
+                Term::Age(age) => {
+                    others.push(Box::new(SuitableForAge::new(age, Unknown::Exclude)));
+                }
+                Term::Day(day) => others.push(Box::new(OnDay::new(day, rollover_hour))),
+                term => matches.push(vec![term]),
+            }
+        }
+
+        if !ids.is_empty() {
+            matches.push(ids);
+        }
+
+        if !venues.is_empty() {
+            others.push(Box::new(AtVenues::new(venues)));
+        }
+
+        if !kinds.is_empty() {
+            others.push(Box::new(OfKinds::new(kinds)));
+        }
+
+        // Terms that need to look at the whole schedule go first, before anything is removed
+        let mut mutators = Mutators::default();
+        if !matches.is_empty() {
+            mutators.push(Box::new(Matches {
+                groups: matches,
+                rollover_hour,
+            }));
+        }
+        for mutator in others {
+            mutators.push(mutator);
+        }
+
+        mutators
+    }
+}
+
+impl Term {
+    fn matches(&self, event: &Event, dates: &ScheduleDates, rollover_hour: u32) -> bool {
+        match self {
+            Term::Venue(venue) => event.venue == *venue,
+            Term::Kind(kind) => event.kind.type_name().eq_ignore_ascii_case(kind),
+            Term::Id(id) => event.id == *id,
+            Term::Title(s) => contains_ignore_case(&event.title, s),
+            Term::Speaker(s) => contains_ignore_case(&event.speaker, s),
+            Term::FamilyFriendly => event.is_family_friendly == Some(true),
+            Term::MayRecord => event.may_record == Some(true),
+            Term::Free => event.kind.workshop().and_then(|w| w.is_free()) == Some(true),
+            Term::Age(age) => {
+                event.kind.workshop().and_then(|w| w.suitable_for_age(*age)) == Some(true)
+            }
+            Term::Day(day) => day.matches(FestivalDay::of(event.start, rollover_hour)),
+            Term::Start(comparison, time) => dates
+                .resolve(time)
+                .is_some_and(|t| comparison.compare(event.start, t)),
+            Term::End(comparison, time) => dates
+                .resolve(time)
+                .is_some_and(|t| comparison.compare(event.end, t)),
+            Term::Not(term) => !term.matches(event, dates, rollover_hour),
+        }
+    }
+}
+
+impl Comparison {
+    fn compare<T: Ord>(&self, value: T, other: T) -> bool {
+        match self {
+            Comparison::Before => value < other,
+            Comparison::BeforeOrAt => value <= other,
+            Comparison::After => value > other,
+            Comparison::AtOrAfter => value >= other,
+        }
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// Keeps events that match at least one term from every group.
+struct Matches {
+    groups: Vec<Vec<Term>>,
+    rollover_hour: u32,
+}
+
+impl Mutator for Matches {
+    fn mutate(&self, events: &mut Vec<Event>) {
+        let dates = ScheduleDates::new(events);
+        events.retain(|event| {
+            self.groups.iter().all(|any_of| {
+                any_of
+                    .iter()
+                    .any(|term| term.matches(event, &dates, self.rollover_hour))
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::{
+        Schedule,
+        day::DEFAULT_ROLLOVER_HOUR,
+        event::{Kind, Workshop},
+    };
+    use chrono::DateTime;
+
+    fn schedule() -> Schedule {
+        let event = |id, start: &str, venue: &str, kind: Kind| {
+            let mut e = Event::dummy(id, DateTime::parse_from_rfc3339(start).unwrap());
+            e.venue = venue.to_owned();
+            e.title = format!("Event {id}");
+            e.kind = kind;
+            e
+        };
+
+        let mut family_friendly = event(3, "2024-06-01T10:00:00+01:00", "Stage A", Kind::Talk);
+        family_friendly.is_family_friendly = Some(true);
+
+        Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", "Stage A", Kind::Talk),
+                event(1, "2024-05-31T12:00:00+01:00", "Stage B", Kind::Performance),
+                event(2, "2024-06-01T09:00:00+01:00", "Stage B", Kind::Talk),
+                family_friendly,
+                event(4, "2024-06-01T14:00:00+01:00", "Stage C", Kind::Performance),
+            ],
+        }
+    }
+
+    fn ids(query: &str) -> Vec<u32> {
+        filtered_ids(schedule(), query)
+    }
+
+    fn filtered_ids(schedule: Schedule, query: &str) -> Vec<u32> {
+        filtered_ids_with_rollover(schedule, query, DEFAULT_ROLLOVER_HOUR)
+    }
+
+    fn filtered_ids_with_rollover(
+        mut schedule: Schedule,
+        query: &str,
+        rollover_hour: u32,
+    ) -> Vec<u32> {
+        let query = MatchesQuery::new(Query::parse(query).unwrap(), rollover_hour);
+        schedule.mutate(&Mutators::new_single(Box::new(query)));
+        schedule.events.iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for query in [
+            "",
+            "venue:\"Stage A\" kind:workshop -id:4",
+            "title:\"say \\\"hello\\\"\" speaker:back\\slash family_friendly -may_record",
+            "free -age:12",
+            "day:sat -day:2024-06-02",
+            "start>=sat10:00 end<2024-06-01T18:00:00+01:00 -start<=fri09:30 end>sun00:00",
+        ] {
+            let parsed = Query::parse(query).unwrap();
+            assert_eq!(parsed.to_string(), query);
+            assert_eq!(Query::parse(&parsed.to_string()).unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(ids(""), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn venues() {
+        assert_eq!(ids("venue:\"Stage A\""), vec![0, 3]);
+        assert_eq!(ids("venue:\"Stage A\" venue:\"Stage C\""), vec![0, 3, 4]);
+        assert_eq!(ids("-venue:\"Stage A\""), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn kinds_and_ids() {
+        assert_eq!(ids("kind:performance"), vec![1, 4]);
+        assert_eq!(ids("type:Talk id:0 id:4"), vec![0]);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(ids("title:\"event 3\""), vec![3]);
+        assert_eq!(ids("-title:EVENT"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(ids("family_friendly"), vec![3]);
+        assert_eq!(ids("-family_friendly kind:talk"), vec![0, 2]);
+    }
+
+    #[test]
+    fn workshops() {
+        let workshop = |id, kind: fn(Workshop) -> Kind, cost: &str, age_range: &str| {
+            let mut e = Event::dummy(
+                id,
+                DateTime::parse_from_rfc3339("2024-06-02T10:00:00+01:00").unwrap(),
+            );
+            e.kind = kind(Workshop {
+                cost: cost.to_owned(),
+                equiptment: None,
+                age_range: age_range.to_owned(),
+                attendees: None,
+            });
+            e
+        };
+
+        let schedule = Schedule {
+            events: vec![
+                Event::dummy(
+                    0,
+                    DateTime::parse_from_rfc3339("2024-06-02T09:00:00+01:00").unwrap(),
+                ),
+                workshop(1, Kind::Workshop, "Free", "8+"),
+                workshop(2, Kind::YouthWorkshop, "£5", "12-16"),
+            ],
+        };
+        let ids = |query| filtered_ids(schedule.clone(), query);
+
+        assert_eq!(ids("free"), vec![1]);
+        assert_eq!(ids("-free"), vec![0, 2]);
+        assert_eq!(ids("age:8"), vec![1]);
+        assert_eq!(ids("age:14"), vec![1, 2]);
+        assert_eq!(ids("-age:8 kind:workshop kind:youthworkshop"), vec![2]);
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(ids("start>=sat10:00"), vec![3, 4]);
+        assert_eq!(ids("start>sat10:00"), vec![4]);
+        assert_eq!(ids("start<\"friday 12:00\""), vec![0]);
+        assert_eq!(ids("end<=2024-05-31T13:00:00+01:00"), vec![0, 1]);
+        assert_eq!(
+            ids("start>2024-06-01T09:00:00+01:00 venue:\"Stage C\""),
+            vec![4]
+        );
+    }
+
+    #[test]
+    fn days_resolved_before_filtering() {
+        assert_eq!(
+            ids("-venue:\"Stage A\" -venue:\"Stage B\" start>=fri00:00"),
+            vec![4]
+        );
+        assert_eq!(ids("start>=sat00:00 end<fri12:00"), Vec::<u32>::new());
+        assert_eq!(ids("end<sat00:00 start>=fri11:00"), vec![1]);
+    }
+
+    #[test]
+    fn festival_days() {
+        assert_eq!(ids("day:friday"), vec![0, 1]);
+        assert_eq!(ids("-day:fri"), vec![2, 3, 4]);
+        assert_eq!(ids("day:2024-06-01 venue:\"Stage B\""), vec![2]);
+    }
+
+    #[test]
+    fn festival_days_with_rollover() {
+        let with_rollover = |query: &str, rollover_hour| {
+            filtered_ids_with_rollover(schedule(), query, rollover_hour)
+        };
+
+        // The 9am event on Saturday is part of Friday when days end at 10am
+        assert_eq!(with_rollover("day:fri", 10), vec![0, 1, 2]);
+        assert_eq!(with_rollover("-day:fri", 10), vec![3, 4]);
+        assert_eq!(with_rollover("-day:fri id:2 id:3", 10), vec![3]);
+        assert_eq!(with_rollover("day:fri", DEFAULT_ROLLOVER_HOUR), vec![0, 1]);
+    }
+
+    #[test]
+    fn spec() {
+        let query = Query::parse("day:fri kind:talk").unwrap();
+        assert_eq!(
+            MatchesQuery::new(query.clone(), 10).spec(),
+            Some(MutatorSpec::Query {
+                query,
+                rollover_hour: 10
+            })
+        );
+    }
+
+    #[test]
+    fn day_not_in_schedule() {
+        assert_eq!(ids("start>mon10:00"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn combined() {
+        assert_eq!(
+            ids("venue:\"Stage B\" start>=sat00:00 -kind:performance"),
+            vec![2]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use super::{Comparison, Term, TimeSpec};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} (at column {column})")]
+pub struct ParseError {
+    /// Position in the query the error was found at, counting characters from 1.
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Colon,
+    Compare(Comparison),
+}
+
+pub(super) fn parse(input: &str) -> Result<Vec<Term>, ParseError> {
+    let mut parser = Parser { input, pos: 0 };
+    let mut terms = Vec::new();
+
+    loop {
+        parser.skip_whitespace();
+        if parser.at_end() {
+            return Ok(terms);
+        }
+        terms.push(parser.term()?);
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error_at(&self, pos: usize, message: String) -> ParseError {
+        ParseError {
+            column: self.input[..pos].chars().count() + 1,
+            message,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.input[start..self.pos]
+    }
+
+    fn term(&mut self) -> Result<Term, ParseError> {
+        if self.peek() != Some('-') {
+            return self.positive_term();
+        }
+
+        self.pos += 1;
+        if self.peek() == Some('-') {
+            return Err(self.error_at(self.pos, "a term can only be negated once".to_owned()));
+        }
+        Ok(Term::Not(Box::new(self.positive_term()?)))
+    }
+
+    fn positive_term(&mut self) -> Result<Term, ParseError> {
+        let start = self.pos;
+
+        let field = self
+            .take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+            .to_owned();
+        if field.is_empty() {
+            return Err(self.error_at(start, "expected a field name".to_owned()));
+        }
+
+        let operator_pos = self.pos;
+        let operator = self.operator();
+
+        match (field.as_str(), operator) {
+            ("family_friendly", None) => Ok(Term::FamilyFriendly),
+            ("may_record", None) => Ok(Term::MayRecord),
+            ("free", None) => Ok(Term::Free),
+            ("family_friendly" | "may_record" | "free", Some(_)) => {
+                Err(self.error_at(operator_pos, format!("`{field}` does not take a value")))
+            }
+
+            (
+                "venue" | "kind" | "type" | "id" | "age" | "day" | "title" | "speaker",
+                Some(Operator::Colon),
+            ) => {
+                let value_pos = self.pos;
+                let value = self.value()?;
+
+                match field.as_str() {
+                    "venue" => Ok(Term::Venue(value)),
+                    "kind" | "type" => Ok(Term::Kind(value)),
+                    "id" => value.parse().map(Term::Id).map_err(|_| {
+                        self.error_at(value_pos, format!("invalid event ID `{value}`"))
+                    }),
+                    "age" => value
+                        .parse()
+                        .map(Term::Age)
+                        .map_err(|_| self.error_at(value_pos, format!("invalid age `{value}`"))),
+                    "day" => value
+                        .parse()
+                        .map(Term::Day)
+                        .map_err(|e| self.error_at(value_pos, e.to_string())),
+                    "title" => Ok(Term::Title(value)),
+                    "speaker" => Ok(Term::Speaker(value)),
+                    _ => unreachable!(),
+                }
+            }
+            ("venue" | "kind" | "type" | "id" | "age" | "day" | "title" | "speaker", _) => {
+                Err(self.error_at(operator_pos, format!("expected `:` after `{field}`")))
+            }
+
+            ("start" | "end", Some(Operator::Compare(comparison))) => {
+                let value_pos = self.pos;
+                let value = self.value()?;
+
+                let time = TimeSpec::parse(&value).ok_or_else(|| {
+                    self.error_at(
+                        value_pos,
+                        format!(
+                            "invalid time `{value}`, expected e.g. `sat10:00` or an RFC 3339 timestamp"
+                        ),
+                    )
+                })?;
+
+                Ok(match field.as_str() {
+                    "start" => Term::Start(comparison, time),
+                    _ => Term::End(comparison, time),
+                })
+            }
+            ("start" | "end", _) => Err(self.error_at(
+                operator_pos,
+                format!("expected one of `<`, `<=`, `>` or `>=` after `{field}`"),
+            )),
+
+            _ => Err(self.error_at(start, format!("unknown field `{field}`"))),
+        }
+    }
+
+    fn operator(&mut self) -> Option<Operator> {
+        let (operator, len) = match self.rest().as_bytes() {
+            [b':', ..] => (Operator::Colon, 1),
+            [b'<', b'=', ..] => (Operator::Compare(Comparison::BeforeOrAt), 2),
+            [b'>', b'=', ..] => (Operator::Compare(Comparison::AtOrAfter), 2),
+            [b'<', ..] => (Operator::Compare(Comparison::Before), 1),
+            [b'>', ..] => (Operator::Compare(Comparison::After), 1),
+            _ => return None,
+        };
+
+        self.pos += len;
+        Some(operator)
+    }
+
+    fn value(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+
+        if self.peek() != Some('"') {
+            let value = self.take_while(|c| !c.is_whitespace());
+            return if value.is_empty() {
+                Err(self.error_at(start, "expected a value".to_owned()))
+            } else {
+                Ok(value.to_owned())
+            };
+        }
+
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+
+        Err(self.error_at(start, "unterminated quoted value".to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(input: &str) -> (usize, String) {
+        let err = parse(input).unwrap_err();
+        (err.column, err.message)
+    }
+
+    #[test]
+    fn terms() {
+        assert_eq!(
+            parse(r#"  venue:"Stage \"A\"" -kind:talk  family_friendly id:4 start<=fri18:00 "#)
+                .unwrap(),
+            vec![
+                Term::Venue("Stage \"A\"".to_owned()),
+                Term::Not(Box::new(Term::Kind("talk".to_owned()))),
+                Term::FamilyFriendly,
+                Term::Id(4),
+                Term::Start(Comparison::BeforeOrAt, TimeSpec::parse("fri18:00").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            error("colour:red"),
+            (1, "unknown field `colour`".to_owned())
+        );
+        assert_eq!(
+            error("kind:talk :x"),
+            (11, "expected a field name".to_owned())
+        );
+        assert_eq!(error("venue"), (6, "expected `:` after `venue`".to_owned()));
+        assert_eq!(error("venue: x"), (7, "expected a value".to_owned()));
+        assert_eq!(
+            error("venue:\"Stage A"),
+            (7, "unterminated quoted value".to_owned())
+        );
+        assert_eq!(
+            error("day:someday"),
+            (
+                5,
+                "invalid day `someday`, expected a day of the week or a date".to_owned()
+            )
+        );
+        assert_eq!(error("id:four"), (4, "invalid event ID `four`".to_owned()));
+        assert_eq!(error("age:-1"), (5, "invalid age `-1`".to_owned()));
+        assert_eq!(
+            error("may_record:yes"),
+            (11, "`may_record` does not take a value".to_owned())
+        );
+        assert_eq!(
+            error("start:sat10:00"),
+            (
+                6,
+                "expected one of `<`, `<=`, `>` or `>=` after `start`".to_owned()
+            )
+        );
+        assert_eq!(
+            error("--kind:talk"),
+            (2, "a term can only be negated once".to_owned())
+        );
+        assert_eq!(error("-"), (2, "expected a field name".to_owned()));
+        assert_eq!(
+            error("end>later"),
+            (
+                5,
+                "invalid time `later`, expected e.g. `sat10:00` or an RFC 3339 timestamp"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn long_run_of_negations() {
+        let input = format!("{}kind:talk", "-".repeat(100_000));
+        assert_eq!(
+            error(&input),
+            (2, "a term can only be negated once".to_owned())
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            parse("kind:talk colour:red").unwrap_err().to_string(),
+            "unknown field `colour` (at column 11)"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::schedule::{day::parse_weekday, event::Event};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, SecondsFormat, Weekday};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeSpec {
+    Absolute(DateTime<FixedOffset>),
+
+    /// A time on the first day of the schedule that falls on the given day of the week.
+    DayTime(Weekday, NaiveTime),
+}
+
+impl TimeSpec {
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+            return Some(Self::Absolute(t));
+        }
+
+        let s = s.to_lowercase();
+        let split = s.find(|c: char| !c.is_ascii_alphabetic())?;
+        let (day, time) = s.split_at(split);
+
+        let day = parse_weekday(day)?;
+
+        let time = NaiveTime::parse_from_str(time.trim_start(), "%H:%M").ok()?;
+
+        Some(Self::DayTime(day, time))
+    }
+}
+
+impl fmt::Display for TimeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSpec::Absolute(t) => {
+                write!(f, "{}", t.to_rfc3339_opts(SecondsFormat::AutoSi, false))
+            }
+            TimeSpec::DayTime(day, time) => write!(
+                f,
+                "{}{}",
+                day.to_string().to_lowercase(),
+                time.format("%H:%M")
+            ),
+        }
+    }
+}
+
+/// The dates on which events in the schedule start, used to resolve [`TimeSpec::DayTime`].
+pub(super) struct ScheduleDates {
+    days: Vec<(NaiveDate, FixedOffset)>,
+}
+
+impl ScheduleDates {
+    pub(super) fn new(events: &[Event]) -> Self {
+        let mut days: Vec<_> = events
+            .iter()
+            .map(|e| (e.start.date_naive(), *e.start.offset()))
+            .collect();
+        days.sort_by_key(|(date, _)| *date);
+        days.dedup_by_key(|(date, _)| *date);
+
+        Self { days }
+    }
+
+    /// Gives the absolute time a [`TimeSpec`] refers to, or `None` if no event starts on the day
+    /// it refers to.
+    pub(super) fn resolve(&self, time: &TimeSpec) -> Option<DateTime<FixedOffset>> {
+        match time {
+            TimeSpec::Absolute(t) => Some(*t),
+            TimeSpec::DayTime(weekday, time) => self
+                .days
+                .iter()
+                .find(|(date, _)| date.weekday() == *weekday)
+                .and_then(|(date, offset)| {
+                    date.and_time(*time).and_local_timezone(*offset).single()
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let ten = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+
+        assert_eq!(
+            TimeSpec::parse("sat10:00"),
+            Some(TimeSpec::DayTime(Weekday::Sat, ten))
+        );
+        assert_eq!(
+            TimeSpec::parse("Saturday 10:00"),
+            Some(TimeSpec::DayTime(Weekday::Sat, ten))
+        );
+        assert_eq!(
+            TimeSpec::parse("2024-06-01T10:00:00+01:00"),
+            Some(TimeSpec::Absolute(
+                DateTime::parse_from_rfc3339("2024-06-01T10:00:00+01:00").unwrap()
+            ))
+        );
+        assert_eq!(TimeSpec::parse("sat"), None);
+        assert_eq!(TimeSpec::parse("10:00"), None);
+        assert_eq!(TimeSpec::parse("someday10:00"), None);
+        assert_eq!(TimeSpec::parse("sat25:00"), None);
+    }
+}
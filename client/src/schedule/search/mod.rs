@@ -0,0 +1,320 @@
+mod snippet;
+mod tokenize;
+
+pub use self::snippet::Snippet;
+use self::tokenize::Tokenizer;
+use super::{Schedule, event::Event};
+use std::{collections::HashMap, ops::Range};
+
+/// BM25 term frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 field length normalisation.
+const B: f64 = 0.75;
+
+const SNIPPET_LENGTH: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Speaker,
+    Description,
+}
+
+impl Field {
+    const ALL: [Field; 3] = [Field::Title, Field::Speaker, Field::Description];
+
+    /// How much more a match in this field counts for than one in the description.
+    fn weight(&self) -> f64 {
+        match self {
+            Field::Title => 3.0,
+            Field::Speaker => 2.0,
+            Field::Description => 1.0,
+        }
+    }
+
+    fn text<'a>(&self, event: &'a Event) -> &'a str {
+        match self {
+            Field::Title => &event.title,
+            Field::Speaker => &event.speaker,
+            Field::Description => &event.description,
+        }
+    }
+}
+
+/// Occurrences of a term in one field of one event.
+#[derive(Debug)]
+struct Posting {
+    event: usize,
+    field: Field,
+    positions: Vec<Range<usize>>,
+}
+
+/// A full text index over the title, speaker and description of events.
+pub struct SearchIndex {
+    tokenizer: Tokenizer,
+    events: Vec<Event>,
+    postings: HashMap<String, Vec<Posting>>,
+    field_lengths: Vec<[usize; 3]>,
+    average_field_lengths: [f64; 3],
+}
+
+/// An event matching a search, before the snippet is extracted.
+pub(crate) struct Ranked {
+    /// Index of the event in the events the index was built from.
+    pub(crate) event: usize,
+    score: f64,
+    /// Positions of the matches in each field.
+    positions: [Vec<Range<usize>>; 3],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit<'a> {
+    pub event: &'a Event,
+
+    /// Relevance of the event to the search, higher is better.
+    pub score: f64,
+
+    /// Extract from the description (or title or speaker, if the description did not match).
+    pub snippet: Snippet,
+}
+
+impl SearchIndex {
+    pub fn new(events: &[Event]) -> Self {
+        let tokenizer = Tokenizer::default();
+        let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+        let mut field_lengths = Vec::with_capacity(events.len());
+
+        for (i, event) in events.iter().enumerate() {
+            let mut lengths = [0; 3];
+
+            for (f, field) in Field::ALL.into_iter().enumerate() {
+                let tokens = tokenizer.tokenize(field.text(event));
+                lengths[f] = tokens.len();
+
+                let mut terms: HashMap<String, Vec<Range<usize>>> = HashMap::new();
+                for token in tokens {
+                    terms.entry(token.term).or_default().push(token.range);
+                }
+
+                for (term, positions) in terms {
+                    postings.entry(term).or_default().push(Posting {
+                        event: i,
+                        field,
+                        positions,
+                    });
+                }
+            }
+
+            field_lengths.push(lengths);
+        }
+
+        let mut average_field_lengths = [0.0; 3];
+        if !events.is_empty() {
+            for (f, average) in average_field_lengths.iter_mut().enumerate() {
+                let total: usize = field_lengths.iter().map(|lengths| lengths[f]).sum();
+                *average = total as f64 / events.len() as f64;
+            }
+        }
+
+        Self {
+            tokenizer,
+            events: events.to_vec(),
+            postings,
+            field_lengths,
+            average_field_lengths,
+        }
+    }
+
+    /// Finds events matching any of the words in the query, most relevant first.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+        self.ranked(query)
+            .into_iter()
+            .map(|ranked| {
+                let event = &self.events[ranked.event];
+
+                let (field, positions) = [Field::Description, Field::Title, Field::Speaker]
+                    .into_iter()
+                    .map(|field| (field, ranked.positions[field as usize].as_slice()))
+                    .find(|(_, positions)| !positions.is_empty())
+                    .unwrap_or((Field::Description, &[]));
+
+                SearchHit {
+                    event,
+                    score: ranked.score,
+                    snippet: Snippet::new(field.text(event), positions, SNIPPET_LENGTH),
+                }
+            })
+            .collect()
+    }
+
+    /// Events matching the query, most relevant first.
+    pub(crate) fn ranked(&self, query: &str) -> Vec<Ranked> {
+        let mut terms: Vec<_> = self
+            .tokenizer
+            .tokenize(query)
+            .into_iter()
+            .map(|t| t.term)
+            .collect();
+        terms.sort();
+        terms.dedup();
+
+        let mut results: HashMap<usize, Ranked> = HashMap::new();
+
+        for term in terms {
+            let Some(postings) = self.postings.get(&term) else {
+                continue;
+            };
+
+            let idf = self.inverse_document_frequency(postings);
+
+            for posting in postings {
+                let f = posting.field as usize;
+                let tf = posting.positions.len() as f64;
+                let length = self.field_lengths[posting.event][f] as f64;
+                let norm = 1.0 - B + B * length / self.average_field_lengths[f];
+
+                let result = results.entry(posting.event).or_insert_with(|| Ranked {
+                    event: posting.event,
+                    score: 0.0,
+                    positions: Default::default(),
+                });
+                result.score += idf * posting.field.weight() * tf * (K1 + 1.0) / (tf + K1 * norm);
+                result.positions[f].extend(posting.positions.iter().cloned());
+            }
+        }
+
+        let mut results: Vec<_> = results.into_values().collect();
+        for result in &mut results {
+            for positions in &mut result.positions {
+                positions.sort_by_key(|r| r.start);
+            }
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| self.events[a.event].cmp(&self.events[b.event]))
+                .then_with(|| a.event.cmp(&b.event))
+        });
+
+        results
+    }
+
+    fn inverse_document_frequency(&self, postings: &[Posting]) -> f64 {
+        let mut events: Vec<_> = postings.iter().map(|p| p.event).collect();
+        events.dedup();
+
+        let n = self.events.len() as f64;
+        let matching = events.len() as f64;
+        ((n - matching + 0.5) / (matching + 0.5) + 1.0).ln()
+    }
+}
+
+impl Schedule {
+    pub fn search_index(&self) -> SearchIndex {
+        SearchIndex::new(&self.events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    fn schedule() -> Schedule {
+        let event = |id, title: &str, speaker: &str, description: &str| {
+            let mut e = Event::dummy(
+                id,
+                DateTime::parse_from_rfc3339("2024-05-31T10:00:00+01:00").unwrap(),
+            );
+            e.title = title.to_owned();
+            e.speaker = speaker.to_owned();
+            e.description = description.to_owned();
+            e
+        };
+
+        Schedule {
+            events: vec![
+                event(
+                    0,
+                    "Soldering for beginners",
+                    "Alice",
+                    "Learn to solder a small kit.",
+                ),
+                event(
+                    1,
+                    "Long range radio",
+                    "Bob",
+                    "An introduction to LoRa and building LoRa gateways.",
+                ),
+                event(
+                    2,
+                    "LoRa mesh networking",
+                    "Carol",
+                    "Meshtastic, what it is and how it works.",
+                ),
+                event(3, "Radio controlled soldering", "Lora Smith", ""),
+            ],
+        }
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<u32> {
+        hits.iter().map(|h| h.event.id).collect()
+    }
+
+    #[test]
+    fn ranking() {
+        let schedule = schedule();
+        let index = schedule.search_index();
+
+        // Title matches rank above speaker matches, which rank above description matches
+        let hits = index.search("that talk about LoRa");
+        assert_eq!(ids(&hits), vec![2, 3, 1]);
+        assert!(hits[0].score > hits[1].score);
+        assert!(hits[1].score > hits[2].score);
+    }
+
+    #[test]
+    fn stemming_and_case() {
+        let schedule = schedule();
+        let index = schedule.search_index();
+
+        assert_eq!(ids(&index.search("SOLDER")), vec![0, 3]);
+        assert_eq!(ids(&index.search("network")), vec![2]);
+    }
+
+    #[test]
+    fn multiple_terms() {
+        let schedule = schedule();
+        let index = schedule.search_index();
+
+        assert_eq!(ids(&index.search("radio gateways")), vec![1, 3]);
+    }
+
+    #[test]
+    fn snippets() {
+        let schedule = schedule();
+        let index = schedule.search_index();
+
+        let hits = index.search("lora");
+        assert_eq!(
+            hits[0].snippet.highlighted("[", "]"),
+            "[LoRa] mesh networking"
+        );
+        assert_eq!(hits[1].snippet.highlighted("[", "]"), "[Lora] Smith");
+        assert_eq!(
+            hits[2].snippet.highlighted("[", "]"),
+            "An introduction to [LoRa] and building [LoRa] gateways."
+        );
+    }
+
+    #[test]
+    fn no_matches() {
+        let schedule = schedule();
+        let index = schedule.search_index();
+
+        assert!(index.search("knitting").is_empty());
+        assert!(index.search("").is_empty());
+        assert!(SearchIndex::new(&[]).search("lora").is_empty());
+    }
+}
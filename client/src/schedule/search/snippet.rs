@@ -0,0 +1,212 @@
+use serde::Serialize;
+use std::ops::Range;
+
+const ELLIPSIS: &str = "…";
+
+/// An extract of the text of an event with the words that matched a search marked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Snippet {
+    pub text: String,
+
+    /// Positions of the matched words in `text`, in bytes.
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl Snippet {
+    /// Takes up to around `max_len` bytes of `source` around the first match, collapsing runs of
+    /// whitespace (e.g. paragraph breaks in descriptions) to a single space.
+    pub(super) fn new(source: &str, matches: &[Range<usize>], max_len: usize) -> Self {
+        let window = window(source, matches.first().map_or(0, |m| m.start), max_len);
+
+        let mut text = String::new();
+        let mut highlights = Vec::new();
+        let mut highlight_start = None;
+
+        if window.start > 0 {
+            text.push_str(ELLIPSIS);
+            text.push(' ');
+        }
+
+        // Also skips any leading whitespace
+        let mut last_was_whitespace = true;
+        for (i, c) in source[window.clone()].char_indices() {
+            let i = i + window.start;
+
+            if c.is_whitespace() {
+                if !last_was_whitespace {
+                    text.push(' ');
+                }
+                last_was_whitespace = true;
+                continue;
+            }
+            last_was_whitespace = false;
+
+            if matches.iter().any(|m| m.start == i) {
+                highlight_start = Some(text.len());
+            }
+
+            text.push(c);
+
+            if let Some(start) = highlight_start
+                && matches.iter().any(|m| m.end == i + c.len_utf8())
+            {
+                highlights.push(start..text.len());
+                highlight_start = None;
+            }
+        }
+
+        if window.end < source.trim_end().len() {
+            text.push(' ');
+            text.push_str(ELLIPSIS);
+        }
+
+        Self { text, highlights }
+    }
+
+    /// Parts of the text, along with whether they were matched.
+    pub fn parts(&self) -> Vec<(&str, bool)> {
+        let mut parts = Vec::new();
+        let mut last = 0;
+
+        for highlight in &self.highlights {
+            if highlight.start > last {
+                parts.push((&self.text[last..highlight.start], false));
+            }
+            parts.push((&self.text[highlight.clone()], true));
+            last = highlight.end;
+        }
+
+        if last < self.text.len() {
+            parts.push((&self.text[last..], false));
+        }
+
+        parts
+    }
+
+    /// The text with each match surrounded by `before` and `after` (e.g. `<mark>` and `</mark>`).
+    pub fn highlighted(&self, before: &str, after: &str) -> String {
+        self.parts()
+            .into_iter()
+            .map(|(part, matched)| {
+                if matched {
+                    format!("{before}{part}{after}")
+                } else {
+                    part.to_owned()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Picks the part of the text to show, starting a little before the given position and breaking
+/// on whitespace.
+fn window(source: &str, around: usize, max_len: usize) -> Range<usize> {
+    let source = source.trim_end();
+    if source.len() <= max_len {
+        return 0..source.len();
+    }
+
+    let mut start = floor_char_boundary(source, around.saturating_sub(max_len / 4));
+    if start > 0 {
+        start = source[..start]
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+    }
+
+    let mut end = floor_char_boundary(source, start + max_len);
+    if end < source.len()
+        && let Some(i) = source[start..end].rfind(char::is_whitespace)
+    {
+        end = start + i;
+    }
+
+    start..end
+}
+
+/// The closest position at or before `i` that is not in the middle of a character.
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    i = i.min(s.len());
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn whole_text() {
+        let snippet = Snippet::new(" Building LoRa\n\ngateways\n", &[10..14, 16..24], 100);
+
+        assert_eq!(snippet.text, "Building LoRa gateways");
+        assert_eq!(snippet.highlighted("[", "]"), "Building [LoRa] [gateways]");
+        assert_eq!(
+            snippet.parts(),
+            vec![
+                ("Building ", false),
+                ("LoRa", true),
+                (" ", false),
+                ("gateways", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let source = "one two three four five six seven eight nine ten eleven twelve";
+        let start = source.find("seven").unwrap();
+
+        let matches = [Range {
+            start,
+            end: start + 5,
+        }];
+
+        let snippet = Snippet::new(source, &matches, 24);
+
+        assert_eq!(snippet.highlighted("[", "]"), "… five six [seven] eight …");
+    }
+
+    #[test]
+    fn multibyte_text_before_match() {
+        let source =
+            "It’s a workshop about making things, it’s great fun and you’ll learn to solder.";
+        let start = source.find("solder").unwrap();
+
+        let matches = [Range {
+            start,
+            end: start + 6,
+        }];
+
+        for max_len in 10..source.len() {
+            let snippet = Snippet::new(source, &matches, max_len);
+            assert!(snippet.text.contains("solder"));
+        }
+
+        let snippet = Snippet::new(source, &matches, 30);
+        assert_eq!(snippet.highlighted("[", "]"), "… learn to [solder].");
+    }
+
+    #[test]
+    fn multibyte_whitespace() {
+        let source = "one\u{a0}two\u{a0}three\u{a0}four\u{a0}five";
+        let start = source.find("five").unwrap();
+
+        let matches = [Range {
+            start,
+            end: start + 4,
+        }];
+
+        let snippet = Snippet::new(source, &matches, 10);
+        assert_eq!(snippet.highlighted("[", "]"), "… four [five]");
+    }
+
+    #[test]
+    fn no_matches() {
+        let snippet = Snippet::new("one two three four", &[], 10);
+        assert_eq!(snippet.text, "one two …");
+        assert!(snippet.highlights.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use rust_stemmers::{Algorithm, Stemmer};
+use std::ops::Range;
+
+/// Words too common to be useful when searching, these are not indexed.
+const STOP_WORDS: [&str; 32] = [
+    "a", "about", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "i", "in",
+    "is", "it", "of", "on", "or", "that", "the", "this", "to", "was", "we", "what", "when",
+    "where", "who", "will", "with", "you",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Token {
+    /// The case folded and stemmed form of the word.
+    pub(super) term: String,
+
+    /// Position of the word in the original text, in bytes.
+    pub(super) range: Range<usize>,
+}
+
+pub(super) struct Tokenizer {
+    stemmer: Stemmer,
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self {
+            stemmer: Stemmer::create(Algorithm::English),
+        }
+    }
+}
+
+impl Tokenizer {
+    pub(super) fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+
+        for (i, c) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (c.is_alphanumeric(), start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    start = None;
+
+                    let word = text[s..i].to_lowercase();
+                    if !STOP_WORDS.contains(&word.as_str()) {
+                        tokens.push(Token {
+                            term: self.stemmer.stem(&word).into_owned(),
+                            range: s..i,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn terms(text: &str) -> Vec<String> {
+        Tokenizer::default()
+            .tokenize(text)
+            .into_iter()
+            .map(|t| t.term)
+            .collect()
+    }
+
+    #[test]
+    fn basic() {
+        let tokens = Tokenizer::default().tokenize("Building LoRa gateways, the easy way!");
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token {
+                    term: "build".to_owned(),
+                    range: 0..8,
+                },
+                Token {
+                    term: "lora".to_owned(),
+                    range: 9..13,
+                },
+                Token {
+                    term: "gateway".to_owned(),
+                    range: 14..22,
+                },
+                Token {
+                    term: "easi".to_owned(),
+                    range: 28..32,
+                },
+                Token {
+                    term: "way".to_owned(),
+                    range: 33..36,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(
+            terms("Café—Über 3D-printing"),
+            vec!["café", "über", "3d", "print"]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(terms("").is_empty());
+        assert!(terms(" -- the ").is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+use super::{Schedule, event::Event};
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Every occurrence of an event that runs more than once (e.g. a workshop held each day).
+/// Events that only run once form a group of their own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionGroup {
+    /// Title of the first session.
+    pub title: String,
+
+    /// Each session, in order of start time.
+    pub sessions: Vec<Event>,
+}
+
+impl SessionGroup {
+    /// True if the event runs more than once.
+    pub fn is_repeated(&self) -> bool {
+        self.sessions.len() > 1
+    }
+
+    pub fn first(&self) -> &Event {
+        &self.sessions[0]
+    }
+
+    /// The first session that has not yet started.
+    pub fn next_after(&self, now: DateTime<FixedOffset>) -> Option<&Event> {
+        self.sessions.iter().find(|e| e.start > now)
+    }
+}
+
+/// Words that mark an occurrence rather than being part of the name when they end a title,
+/// optionally followed by a number, e.g. "Intro to Soldering (Session 2)" or "Soldering #2".
+const OCCURRENCE_WORDS: &[&str] = &["session", "repeat", "repeated", "again", "run", "slot", "#"];
+
+/// The form of a title used to tell if two events are occurrences of the same thing.
+fn title_key(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let mut words: Vec<_> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '#')
+        .flat_map(|word| match word.strip_prefix('#') {
+            Some(number) => vec!["#", number],
+            None => vec![word],
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    // A number at the end of a title is only dropped if it follows an occurrence word, otherwise
+    // it is more likely to be part of the name (e.g. "Apollo 11")
+    let is_number = |word: &str| word.chars().all(|c| c.is_ascii_digit());
+    if let [.., previous, last] = words.as_slice()
+        && is_number(last)
+        && OCCURRENCE_WORDS.contains(previous)
+    {
+        words.pop();
+    }
+    while words.last().is_some_and(|w| OCCURRENCE_WORDS.contains(w)) {
+        words.pop();
+    }
+
+    words.join(" ")
+}
+
+/// The slug without the number that the site adds to make the slugs of events with the same title
+/// unique (e.g. `soldering-2`), if it has one.
+fn slug_base(slug: &str) -> Option<&str> {
+    let (base, number) = slug.rsplit_once('-')?;
+    let is_suffix = !base.is_empty()
+        && !number.starts_with('0')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit());
+    is_suffix.then_some(base)
+}
+
+/// What is compared to tell if two events are occurrences of the same thing, worked out once for
+/// each event.
+struct SessionKey<'a> {
+    kind: &'a str,
+    title: String,
+    slug: String,
+    slug_base: Option<String>,
+}
+
+impl<'a> SessionKey<'a> {
+    fn new(event: &'a Event) -> Self {
+        let slug = event.slug.trim().to_lowercase();
+        Self {
+            kind: event.kind.type_name(),
+            title: title_key(&event.title),
+            slug_base: slug_base(&slug).map(str::to_owned),
+            slug,
+        }
+    }
+
+    /// True if two events are occurrences of the same thing: they are the same kind of event, have
+    /// the same title (ignoring case, punctuation and any occurrence number), and have the same
+    /// slug once any number added to make it unique is removed from one or both of them.
+    fn is_repeat_of(&self, other: &Self) -> bool {
+        let same_slug = self.slugs().any(|a| other.slugs().any(|b| a == b));
+
+        self.kind == other.kind
+            && !self.title.is_empty()
+            && self.title == other.title
+            && !self.slug.is_empty()
+            && same_slug
+    }
+
+    fn slugs(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.slug.as_str()).chain(self.slug_base.as_deref())
+    }
+}
+
+impl Schedule {
+    /// Groups events that are occurrences of the same workshop, performance, etc., ordered by the
+    /// start of their first session.
+    pub fn session_groups(&self) -> Vec<SessionGroup> {
+        let mut events: Vec<_> = self.events.iter().collect();
+        events.sort_by_key(|e| (e.start, e.id));
+
+        let mut groups: Vec<(SessionGroup, Vec<SessionKey>)> = Vec::new();
+
+        // Repeats have the same kind and title, so only groups with those need to be checked
+        let mut by_title: HashMap<(&str, String), Vec<usize>> = HashMap::new();
+
+        for event in events {
+            let key = SessionKey::new(event);
+            let candidates = by_title.entry((key.kind, key.title.clone())).or_default();
+
+            let found = candidates
+                .iter()
+                .copied()
+                .find(|&i| groups[i].1.iter().any(|k| k.is_repeat_of(&key)));
+
+            match found {
+                Some(i) => {
+                    let (group, keys) = &mut groups[i];
+                    group.sessions.push(event.clone());
+                    keys.push(key);
+                }
+                None => {
+                    candidates.push(groups.len());
+                    groups.push((
+                        SessionGroup {
+                            title: event.title.clone(),
+                            sessions: vec![event.clone()],
+                        },
+                        vec![key],
+                    ));
+                }
+            }
+        }
+
+        groups.into_iter().map(|(group, _)| group).collect()
+    }
+
+    /// The other occurrences of an event, in order of start time.
+    pub fn other_sessions(&self, event: &Event) -> Vec<Event> {
+        self.session_groups()
+            .into_iter()
+            .find(|g| g.sessions.iter().any(|e| e.id == event.id))
+            .map(|g| {
+                g.sessions
+                    .into_iter()
+                    .filter(|e| e.id != event.id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::Kind;
+
+    fn event(id: u32, start: &str, title: &str, slug: &str) -> Event {
+        let mut e = Event::dummy(id, DateTime::parse_from_rfc3339(start).unwrap());
+        e.title = title.to_owned();
+        e.slug = slug.to_owned();
+        e
+    }
+
+    fn schedule() -> Schedule {
+        Schedule {
+            events: vec![
+                event(
+                    0,
+                    "2024-06-01T10:00:00+01:00",
+                    "Intro to Soldering",
+                    "soldering",
+                ),
+                event(
+                    1,
+                    "2024-05-31T10:00:00+01:00",
+                    "Intro to soldering",
+                    "soldering-2",
+                ),
+                event(
+                    2,
+                    "2024-06-02T10:00:00+01:00",
+                    "Intro to Soldering (Session 3)",
+                    "soldering-3",
+                ),
+                event(3, "2024-05-31T12:00:00+01:00", "LAN party", "lan-party"),
+                event(
+                    4,
+                    "2024-06-01T12:00:00+01:00",
+                    "LAN Party - repeat",
+                    "lan-party-2",
+                ),
+                event(5, "2024-05-31T14:00:00+01:00", "Knitting", "knitting"),
+            ],
+        }
+    }
+
+    #[test]
+    fn title_keys() {
+        assert_eq!(
+            title_key("Intro to Soldering (Session 2)"),
+            "intro to soldering"
+        );
+        assert_eq!(title_key("Soldering #2"), "soldering");
+        assert_eq!(title_key("Soldering - repeat"), "soldering");
+        assert_eq!(title_key("Apollo 11"), "apollo 11");
+        assert_eq!(title_key("Soldering 101"), "soldering 101");
+        assert_eq!(title_key("Session"), "");
+    }
+
+    #[test]
+    fn slug_bases() {
+        assert_eq!(slug_base("soldering-2"), Some("soldering"));
+        assert_eq!(slug_base("apollo-11-2"), Some("apollo-11"));
+        assert_eq!(slug_base("soldering"), None);
+        assert_eq!(slug_base("lan-party"), None);
+        assert_eq!(slug_base("-2"), None);
+        assert_eq!(slug_base("soldering-02"), None);
+    }
+
+    #[test]
+    fn groups() {
+        let groups = schedule().session_groups();
+
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.title.as_str(),
+                    g.sessions.iter().map(|e| e.id).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Intro to soldering", vec![1, 0, 2]),
+                ("LAN party", vec![3, 4]),
+                ("Knitting", vec![5]),
+            ]
+        );
+
+        assert!(groups[0].is_repeated());
+        assert!(!groups[2].is_repeated());
+
+        let now = DateTime::parse_from_rfc3339("2024-06-01T10:00:00+01:00").unwrap();
+        assert_eq!(groups[0].next_after(now).unwrap().id, 2);
+        assert_eq!(groups[2].next_after(now), None);
+    }
+
+    #[test]
+    fn different_kinds_are_not_grouped() {
+        let mut schedule = schedule();
+        schedule.events[0].kind = Kind::Performance;
+
+        let groups = schedule.session_groups();
+        assert_eq!(groups[0].sessions.len(), 2);
+        assert!(
+            groups
+                .iter()
+                .any(|g| g.sessions.len() == 1 && g.first().id == 0)
+        );
+    }
+
+    #[test]
+    fn numbered_titles_are_not_grouped() {
+        let schedule = Schedule {
+            events: vec![
+                event(
+                    0,
+                    "2024-05-31T10:00:00+01:00",
+                    "Soldering 101",
+                    "soldering-101",
+                ),
+                event(
+                    1,
+                    "2024-05-31T12:00:00+01:00",
+                    "Soldering 102",
+                    "soldering-102",
+                ),
+                event(2, "2024-05-31T14:00:00+01:00", "Apollo 11", "apollo-11"),
+                event(3, "2024-05-31T16:00:00+01:00", "Apollo 13", "apollo-13"),
+                event(4, "2024-06-01T16:00:00+01:00", "Apollo 11", "apollo-11-2"),
+            ],
+        };
+
+        let groups: Vec<Vec<_>> = schedule
+            .session_groups()
+            .iter()
+            .map(|g| g.sessions.iter().map(|e| e.id).collect())
+            .collect();
+        assert_eq!(groups, vec![vec![0], vec![1], vec![2, 4], vec![3]]);
+    }
+
+    #[test]
+    fn title_and_slug_must_both_match() {
+        let schedule = Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", "Knitting", "knitting"),
+                event(1, "2024-06-01T10:00:00+01:00", "Knitting", "crochet"),
+                event(2, "2024-06-02T10:00:00+01:00", "Crochet", "knitting-2"),
+            ],
+        };
+
+        assert!(schedule.session_groups().iter().all(|g| !g.is_repeated()));
+    }
+
+    #[test]
+    fn other_sessions() {
+        let schedule = schedule();
+
+        let others: Vec<_> = schedule
+            .other_sessions(&schedule.events[0])
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(others, vec![1, 2]);
+
+        assert!(schedule.other_sessions(&schedule.events[5]).is_empty());
+    }
+}
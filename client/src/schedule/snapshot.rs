@@ -0,0 +1,146 @@
+use super::{
+    Schedule,
+    decode::{DecodedSchedule, Decoding},
+};
+use chrono_tz::Tz;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Loading and saving of schedules in the same JSON format that the upstream API uses.
+/// Allows working from a previously downloaded copy of the schedule when there is no network.
+impl Schedule {
+    /// Reads a schedule, in the same way as [`crate::Client`] with the same decoding and timezone
+    /// would.
+    pub fn from_reader<R: Read>(
+        mut reader: R,
+        decoding: Decoding,
+        timezone: Tz,
+    ) -> crate::Result<DecodedSchedule> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        super::decode::decode_schedule(&data, decoding, timezone)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        decoding: Decoding,
+        timezone: Tz,
+    ) -> crate::Result<DecodedSchedule> {
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file), decoding, timezone)
+    }
+
+    pub fn to_writer<W: Write>(&self, writer: W) -> crate::Result<()> {
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> crate::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schedule::event::{DEFAULT_TIMEZONE, Event, Kind};
+    use chrono::DateTime;
+
+    const UPSTREAM_JSON: &str = r#"[
+        {
+            "id": 42,
+            "slug": "an-example-talk",
+            "start_date": "2024-05-31 10:00:00",
+            "end_date": "2024-05-31 10:30:00",
+            "venue": "Stage A",
+            "map_link": "",
+            "title": "An example talk",
+            "speaker": "Someone",
+            "pronouns": "",
+            "description": "Words words words",
+            "type": "talk",
+            "may_record": true,
+            "is_family_friendly": false,
+            "link": "https://www.emfcamp.org/schedule/2024/42-an-example-talk"
+        }
+    ]"#;
+
+    #[test]
+    fn from_upstream_format() {
+        let schedule =
+            Schedule::from_reader(UPSTREAM_JSON.as_bytes(), Decoding::Strict, DEFAULT_TIMEZONE)
+                .unwrap()
+                .schedule;
+
+        assert_eq!(schedule.events.len(), 1);
+
+        let event = &schedule.events[0];
+        assert_eq!(event.id, 42);
+        assert_eq!(
+            event.start,
+            DateTime::parse_from_rfc3339("2024-05-31T10:00:00+01:00").unwrap()
+        );
+        assert_eq!(event.map_link, None);
+        assert_eq!(event.pronouns, None);
+        assert_eq!(event.kind, Kind::Talk);
+    }
+
+    #[test]
+    fn round_trip() {
+        let schedule = Schedule {
+            events: vec![
+                Event::dummy(
+                    0,
+                    DateTime::parse_from_rfc3339("2024-03-12T20:00:00+00:00").unwrap(),
+                ),
+                Event::dummy(
+                    1,
+                    DateTime::parse_from_rfc3339("2024-03-12T21:00:00+01:00").unwrap(),
+                ),
+            ],
+        };
+
+        let mut buffer = Vec::new();
+        schedule.to_writer(&mut buffer).unwrap();
+
+        let loaded = Schedule::from_reader(buffer.as_slice(), Decoding::Strict, DEFAULT_TIMEZONE)
+            .unwrap()
+            .schedule;
+        assert_eq!(loaded, schedule);
+    }
+
+    #[test]
+    fn timezone() {
+        let schedule = Schedule::from_reader(
+            UPSTREAM_JSON.as_bytes(),
+            Decoding::Strict,
+            chrono_tz::Europe::Amsterdam,
+        )
+        .unwrap()
+        .schedule;
+
+        assert_eq!(
+            schedule.events[0].start,
+            DateTime::parse_from_rfc3339("2024-05-31T10:00:00+02:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let data = UPSTREAM_JSON.replace(r#""type": "talk""#, r#""type": 7"#);
+
+        assert!(
+            Schedule::from_reader(data.as_bytes(), Decoding::Strict, DEFAULT_TIMEZONE).is_err()
+        );
+
+        let decoded =
+            Schedule::from_reader(data.as_bytes(), Decoding::Lenient, DEFAULT_TIMEZONE).unwrap();
+        assert!(decoded.schedule.events.is_empty());
+        assert_eq!(decoded.rejected.len(), 1);
+    }
+}
@@ -0,0 +1,162 @@
+use super::{Schedule, event::Event};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A person speaking at (or running) one or more events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Speaker {
+    /// Name of the speaker, with whitespace normalised.
+    pub name: String,
+
+    /// Pronouns of the speaker, only known for events that they are the only speaker of.
+    pub pronouns: Option<String>,
+
+    /// Events the speaker is involved in, in the order they appear in the schedule.
+    pub events: Vec<Event>,
+}
+
+/// Splits the speaker of an event into individual names, e.g. "Alice, Bob and Carol" becomes
+/// "Alice", "Bob" and "Carol".
+pub fn split_speakers(speaker: &str) -> Vec<String> {
+    let words: Vec<_> = speaker.split_whitespace().collect();
+
+    let mut names = Vec::new();
+    let mut name = Vec::new();
+    for word in words {
+        if word.eq_ignore_ascii_case("and") || word == "&" {
+            names.push(std::mem::take(&mut name));
+            continue;
+        }
+
+        let mut parts = word.split(['&', ',']).peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                name.push(part);
+            }
+            if parts.peek().is_some() {
+                names.push(std::mem::take(&mut name));
+            }
+        }
+    }
+    names.push(name);
+
+    names
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .map(|name| name.join(" "))
+        .collect()
+}
+
+/// The form of a name used to tell if two names refer to the same speaker.
+fn key(name: &str) -> String {
+    name.to_lowercase()
+}
+
+impl Schedule {
+    /// Every individual speaker in the schedule, ordered by name.
+    pub fn speakers(&self) -> Vec<Speaker> {
+        let mut speakers: BTreeMap<String, Speaker> = BTreeMap::new();
+
+        for event in &self.events {
+            let names = split_speakers(&event.speaker);
+            let sole_speaker = names.len() == 1;
+
+            for name in names {
+                let speaker = speakers.entry(key(&name)).or_insert_with(|| Speaker {
+                    name,
+                    pronouns: None,
+                    events: Vec::new(),
+                });
+
+                if sole_speaker && speaker.pronouns.is_none() {
+                    speaker.pronouns = event.pronouns.clone();
+                }
+                speaker.events.push(event.clone());
+            }
+        }
+
+        speakers.into_values().collect()
+    }
+
+    /// Finds a speaker by name, ignoring case and differences in whitespace.
+    pub fn speaker(&self, name: &str) -> Option<Speaker> {
+        let name = key(&name.split_whitespace().collect::<Vec<_>>().join(" "));
+        self.speakers()
+            .into_iter()
+            .find(|speaker| key(&speaker.name) == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::DateTime;
+
+    fn schedule() -> Schedule {
+        let event = |id, speaker: &str, pronouns: Option<&str>| {
+            let mut e = Event::dummy(
+                id,
+                DateTime::parse_from_rfc3339("2024-05-31T10:00:00+01:00").unwrap(),
+            );
+            e.speaker = speaker.to_owned();
+            e.pronouns = pronouns.map(str::to_owned);
+            e
+        };
+
+        Schedule {
+            events: vec![
+                event(0, "Alice & Bob", Some("she/her, he/him")),
+                event(1, "  alice ", Some("she/her")),
+                event(2, "Carol, Dave and Bob", None),
+                event(3, "", None),
+            ],
+        }
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(split_speakers("Alice & Bob"), vec!["Alice", "Bob"]);
+        assert_eq!(split_speakers("Alice&Bob"), vec!["Alice", "Bob"]);
+        assert_eq!(
+            split_speakers("Alice Smith, Bob, and  Carol Jones"),
+            vec!["Alice Smith", "Bob", "Carol Jones"]
+        );
+        assert_eq!(split_speakers("Andrew Anderson"), vec!["Andrew Anderson"]);
+        assert_eq!(split_speakers(" , "), Vec::<String>::new());
+        assert_eq!(split_speakers(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn speakers() {
+        let speakers = schedule().speakers();
+
+        let summary: Vec<_> = speakers
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.pronouns.as_deref(),
+                    s.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Alice", Some("she/her"), vec![0, 1]),
+                ("Bob", None, vec![0, 2]),
+                ("Carol", None, vec![2]),
+                ("Dave", None, vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn speaker() {
+        let schedule = schedule();
+
+        assert_eq!(schedule.speaker(" BOB ").unwrap().name, "Bob");
+        assert!(schedule.speaker("Eve").is_none());
+    }
+}
@@ -0,0 +1,254 @@
+use super::{Schedule, event::Event};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Probably a mistake, but the schedule still makes sense.
+    Warning,
+
+    /// Data that cannot be right, and will likely be shown incorrectly.
+    Error,
+}
+
+/// A problem found with the data in a schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "problem", rename_all = "snake_case")]
+pub enum Problem {
+    /// More than one event has the same ID.
+    DuplicateId { id: u32, count: usize },
+
+    /// An event ends before it starts.
+    EndsBeforeStart { id: u32 },
+
+    /// An event ends at the same time it starts.
+    ZeroLength { id: u32 },
+
+    /// An event has no title.
+    EmptyTitle { id: u32 },
+
+    /// Two events take place in the same venue at the same time.
+    Overlap {
+        venue: String,
+        first: u32,
+        second: u32,
+    },
+}
+
+impl Problem {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Problem::DuplicateId { .. } | Problem::EndsBeforeStart { .. } => Severity::Error,
+            Problem::ZeroLength { .. } | Problem::EmptyTitle { .. } | Problem::Overlap { .. } => {
+                Severity::Warning
+            }
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::DuplicateId { id, count } => write!(f, "{count} events have ID {id}"),
+            Problem::EndsBeforeStart { id } => write!(f, "event {id} ends before it starts"),
+            Problem::ZeroLength { id } => write!(f, "event {id} ends at the same time it starts"),
+            Problem::EmptyTitle { id } => write!(f, "event {id} has no title"),
+            Problem::Overlap {
+                venue,
+                first,
+                second,
+            } => write!(f, "events {first} and {second} overlap at {venue}"),
+        }
+    }
+}
+
+/// The problems found with a schedule, see [`Schedule::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidationReport {
+    pub problems: Vec<Problem>,
+}
+
+impl ValidationReport {
+    /// True if no problems were found at all.
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// True if there are no errors, though there may be warnings.
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Problem> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Problem> {
+        self.with_severity(Severity::Warning)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Problem> {
+        self.problems
+            .iter()
+            .filter(move |problem| problem.severity() == severity)
+    }
+}
+
+impl Schedule {
+    /// Checks the schedule for data that is likely to be wrong.
+    pub fn validate(&self) -> ValidationReport {
+        let mut problems = Vec::new();
+
+        let mut id_counts: HashMap<u32, usize> = HashMap::new();
+        for event in &self.events {
+            *id_counts.entry(event.id).or_default() += 1;
+        }
+        let mut duplicates: Vec<_> = id_counts.into_iter().filter(|(_, n)| *n > 1).collect();
+        duplicates.sort();
+        problems.extend(
+            duplicates
+                .into_iter()
+                .map(|(id, count)| Problem::DuplicateId { id, count }),
+        );
+
+        for event in &self.events {
+            if event.end < event.start {
+                problems.push(Problem::EndsBeforeStart { id: event.id });
+            } else if event.end == event.start {
+                problems.push(Problem::ZeroLength { id: event.id });
+            }
+
+            if event.title.trim().is_empty() {
+                problems.push(Problem::EmptyTitle { id: event.id });
+            }
+        }
+
+        problems.extend(overlaps(&self.events));
+
+        ValidationReport { problems }
+    }
+}
+
+/// Finds pairs of events in the same venue whose times overlap.
+/// Events without a venue are not checked.
+fn overlaps(events: &[Event]) -> Vec<Problem> {
+    let mut by_venue: HashMap<&str, Vec<&Event>> = HashMap::new();
+    for event in events.iter().filter(|e| !e.venue.trim().is_empty()) {
+        by_venue.entry(&event.venue).or_default().push(event);
+    }
+
+    let mut venues: Vec<_> = by_venue.into_iter().collect();
+    venues.sort_by_key(|(venue, _)| *venue);
+
+    let mut problems = Vec::new();
+    for (venue, mut events) in venues {
+        events.sort_by_key(|e| (e.start, e.id));
+
+        for (i, first) in events.iter().enumerate() {
+            for second in events[i + 1..]
+                .iter()
+                .take_while(|second| second.start < first.end)
+            {
+                problems.push(Problem::Overlap {
+                    venue: venue.to_owned(),
+                    first: first.id,
+                    second: second.id,
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::event;
+
+    #[test]
+    fn valid() {
+        let schedule = Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", 60, "Stage A"),
+                event(1, "2024-05-31T11:00:00+01:00", 60, "Stage A"),
+                event(2, "2024-05-31T10:30:00+01:00", 60, "Stage B"),
+            ],
+        };
+
+        let report = schedule.validate();
+        assert!(report.is_empty());
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn problems() {
+        let mut untitled = event(3, "2024-05-31T14:00:00+01:00", 30, "Stage B");
+        untitled.title = " ".to_owned();
+
+        let schedule = Schedule {
+            events: vec![
+                event(0, "2024-05-31T10:00:00+01:00", 60, "Stage A"),
+                event(1, "2024-05-31T10:30:00+01:00", 60, "Stage A"),
+                event(2, "2024-05-31T10:45:00+01:00", 5, "Stage A"),
+                event(0, "2024-05-31T12:00:00+01:00", -10, "Stage B"),
+                untitled,
+                event(4, "2024-05-31T15:00:00+01:00", 0, "Stage B"),
+                event(5, "2024-05-31T15:00:00+01:00", 60, ""),
+                event(6, "2024-05-31T15:00:00+01:00", 60, ""),
+            ],
+        };
+
+        let report = schedule.validate();
+        assert_eq!(
+            report.problems,
+            vec![
+                Problem::DuplicateId { id: 0, count: 2 },
+                Problem::EndsBeforeStart { id: 0 },
+                Problem::EmptyTitle { id: 3 },
+                Problem::ZeroLength { id: 4 },
+                Problem::Overlap {
+                    venue: "Stage A".to_owned(),
+                    first: 0,
+                    second: 1,
+                },
+                Problem::Overlap {
+                    venue: "Stage A".to_owned(),
+                    first: 0,
+                    second: 2,
+                },
+                Problem::Overlap {
+                    venue: "Stage A".to_owned(),
+                    first: 1,
+                    second: 2,
+                },
+            ]
+        );
+
+        assert!(!report.is_ok());
+        assert_eq!(report.errors().count(), 2);
+        assert_eq!(report.warnings().count(), 5);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Problem::Overlap {
+                venue: "Stage A".to_owned(),
+                first: 1,
+                second: 2
+            }
+            .to_string(),
+            "events 1 and 2 overlap at Stage A"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_value(Problem::DuplicateId { id: 4, count: 2 }).unwrap(),
+            serde_json::json!({ "problem": "duplicate_id", "id": 4, "count": 2 })
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use super::Schedule;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, path::Path};
+
+/// Details of a venue, identifying it regardless of how it is spelled in the schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VenueInfo {
+    /// Stable identifier for the venue, e.g. `stage-a`.
+    pub id: String,
+
+    /// Name to show for the venue, events at the venue are given this name.
+    pub name: String,
+
+    /// Other names the venue appears under in the schedule.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// Area of the site the venue is in (e.g. "Stages" or "Null Sector").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+
+    /// Position of the venue when listing venues, lowest first.
+    /// Venues without one are listed after those with one, by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i32>,
+}
+
+impl VenueInfo {
+    fn is_called(&self, name: &str) -> bool {
+        let name = normalise(name);
+        [&self.id, &self.name]
+            .into_iter()
+            .chain(&self.aliases)
+            .any(|n| normalise(n) == name)
+    }
+}
+
+/// A set of known venues, as stored in a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VenueRegistry {
+    pub venues: Vec<VenueInfo>,
+}
+
+/// Venues in one area of the site.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Area {
+    /// Name of the area, or `None` for venues that are not in a known area.
+    pub name: Option<String>,
+    pub venues: Vec<String>,
+}
+
+impl VenueRegistry {
+    /// Loads a registry from a TOML file (if the file has a `.toml` extension) or a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        crate::config::load_config(path.as_ref())
+    }
+
+    /// Finds a venue by its ID, name or any of its aliases, ignoring case and differences in
+    /// whitespace.
+    pub fn find(&self, name: &str) -> Option<&VenueInfo> {
+        self.venues.iter().find(|venue| venue.is_called(name))
+    }
+
+    /// The name to use for a venue, which is the name it was given if it is not known.
+    pub fn canonical_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.find(name).map_or(name, |venue| &venue.name)
+    }
+
+    /// Sorts venue names into the order given by the registry.
+    pub fn sort(&self, venues: &mut [String]) {
+        venues.sort_by(|a, b| self.compare(a, b));
+    }
+
+    pub(super) fn compare(&self, a: &str, b: &str) -> Ordering {
+        let key = |name| self.find(name).and_then(|venue| venue.order);
+        match (key(a), key(b)) {
+            (Some(a_order), Some(b_order)) => a_order.cmp(&b_order).then_with(|| a.cmp(b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        }
+    }
+
+    /// Groups venue names by area, with areas ordered by their first venue and venues that are
+    /// not in an area last.
+    pub fn group_by_area(&self, venues: &[String]) -> Vec<Area> {
+        let mut venues = venues.to_vec();
+        self.sort(&mut venues);
+
+        let mut areas: Vec<Area> = Vec::new();
+        let mut no_area = Vec::new();
+
+        for venue in venues {
+            match self.find(&venue).and_then(|v| v.area.clone()) {
+                Some(name) => match areas.iter_mut().find(|a| a.name.as_ref() == Some(&name)) {
+                    Some(area) => area.venues.push(venue),
+                    None => areas.push(Area {
+                        name: Some(name),
+                        venues: vec![venue],
+                    }),
+                },
+                None => no_area.push(venue),
+            }
+        }
+
+        if !no_area.is_empty() {
+            areas.push(Area {
+                name: None,
+                venues: no_area,
+            });
+        }
+
+        areas
+    }
+}
+
+fn normalise(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl Schedule {
+    /// The venues in the schedule, with those known to a registry under the name it gives them
+    /// (whether or not [`CanonicalVenues`](super::mutation::CanonicalVenues) has been applied), in
+    /// the order given by the registry.
+    pub fn canonical_venues(&self, registry: &VenueRegistry) -> Vec<String> {
+        let mut venues: Vec<String> = self
+            .venues()
+            .iter()
+            .map(|venue| registry.canonical_name(venue).to_owned())
+            .collect();
+        registry.sort(&mut venues);
+        venues.dedup();
+        venues
+    }
+
+    /// The canonical venues in the schedule, grouped into the areas given by a registry.
+    pub fn areas(&self, registry: &VenueRegistry) -> Vec<Area> {
+        registry.group_by_area(&self.canonical_venues(registry))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{schedule::event::Event, testing::time};
+
+    fn registry() -> VenueRegistry {
+        toml::from_str(
+            r#"
+            [[venues]]
+            id = "stage-a"
+            name = "Stage A"
+            aliases = ["Stage  A (main)"]
+            area = "Stages"
+            order = 1
+
+            [[venues]]
+            id = "stage-b"
+            name = "Stage B"
+            area = "Stages"
+            order = 2
+
+            [[venues]]
+            id = "workshop-1"
+            name = "Workshop 1"
+            area = "Workshops"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|&n| n.to_owned()).collect()
+    }
+
+    #[test]
+    fn find() {
+        let registry = registry();
+
+        assert_eq!(registry.find("stage a").unwrap().id, "stage-a");
+        assert_eq!(registry.find("STAGE-A").unwrap().id, "stage-a");
+        assert_eq!(registry.find(" Stage A  (Main) ").unwrap().id, "stage-a");
+        assert!(registry.find("Stage C").is_none());
+
+        assert_eq!(registry.canonical_name("stage  b"), "Stage B");
+        assert_eq!(registry.canonical_name("Stage C"), "Stage C");
+    }
+
+    #[test]
+    fn sort() {
+        let mut venues = names(&["Bar", "Workshop 1", "Stage B", "Stage A"]);
+        registry().sort(&mut venues);
+        assert_eq!(venues, names(&["Stage A", "Stage B", "Bar", "Workshop 1"]));
+    }
+
+    #[test]
+    fn group_by_area() {
+        let venues = names(&["Bar", "Workshop 1", "Stage B", "Stage A"]);
+        assert_eq!(
+            registry().group_by_area(&venues),
+            vec![
+                Area {
+                    name: Some("Stages".to_owned()),
+                    venues: names(&["Stage A", "Stage B"]),
+                },
+                Area {
+                    name: Some("Workshops".to_owned()),
+                    venues: names(&["Workshop 1"]),
+                },
+                Area {
+                    name: None,
+                    venues: names(&["Bar"]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn canonical_venues() {
+        let schedule = Schedule {
+            events: ["stage a", "Stage A (Main)", "Bar", "stage b", "Stage A"]
+                .into_iter()
+                .enumerate()
+                .map(|(id, venue)| {
+                    let mut e = Event::dummy(id as u32, time("2024-05-31T10:00:00+01:00"));
+                    e.venue = venue.to_owned();
+                    e
+                })
+                .collect(),
+        };
+
+        assert_eq!(
+            schedule.canonical_venues(&registry()),
+            names(&["Stage A", "Stage B", "Bar"])
+        );
+        assert_eq!(
+            schedule.areas(&registry())[0].venues,
+            names(&["Stage A", "Stage B"])
+        );
+    }
+
+    #[test]
+    fn empty_registry() {
+        let venues = names(&["Stage B", "Stage A"]);
+        assert_eq!(
+            VenueRegistry::default().group_by_area(&venues),
+            vec![Area {
+                name: None,
+                venues: names(&["Stage A", "Stage B"]),
+            }]
+        );
+    }
+}
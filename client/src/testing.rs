@@ -2,20 +2,57 @@ use crate::schedule::event::Event;
 use axum::{
     Json, Router,
     extract::State,
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
     routing::get,
 };
+use chrono::{DateTime, FixedOffset};
 use std::{
     net::SocketAddr,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 use tokio::{net::TcpListener, task::JoinHandle};
 use url::Url;
 
-type Events = Arc<Mutex<Vec<Event>>>;
+/// Parses an RFC 3339 timestamp.
+pub(crate) fn time(s: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(s).unwrap()
+}
+
+/// An event titled after its ID, at a venue from an RFC 3339 start time for some minutes.
+pub(crate) fn event(id: u32, start: &str, minutes: i64, venue: &str) -> Event {
+    let mut e = Event::dummy(id, time(start));
+    e.end = e.start + chrono::Duration::try_minutes(minutes).unwrap();
+    e.venue = venue.to_owned();
+    e.title = format!("Event {id}");
+    e
+}
+
+#[derive(Default)]
+struct ServerState {
+    events: Vec<Event>,
+
+    /// Incremented every time the events are changed, used as the ETag.
+    generation: u64,
+
+    requests: usize,
+    not_modified: usize,
+
+    /// Number of upcoming requests to respond to with a server error.
+    failures: usize,
+
+    /// Time to wait before responding to a request.
+    delay: Duration,
+
+    /// Headers of the most recent request.
+    last_headers: HeaderMap,
+}
+
+type SharedState = Arc<Mutex<ServerState>>;
 
 pub(crate) struct DummyScheduleServer {
-    events: Events,
+    state: SharedState,
 
     port: u16,
     handle: Option<JoinHandle<()>>,
@@ -23,11 +60,11 @@ pub(crate) struct DummyScheduleServer {
 
 impl DummyScheduleServer {
     pub(crate) async fn new(port: u16) -> Self {
-        let events = Arc::new(Mutex::default());
+        let state = Arc::new(Mutex::default());
 
         let app = Router::new()
             .route("/schedule", get(schedule))
-            .with_state(events.clone());
+            .with_state(state.clone());
 
         let address = SocketAddr::new("127.0.0.1".parse().unwrap(), port);
 
@@ -38,7 +75,7 @@ impl DummyScheduleServer {
         }));
 
         Self {
-            events,
+            state,
             port,
             handle,
         }
@@ -49,11 +86,36 @@ impl DummyScheduleServer {
     }
 
     pub(crate) fn set_events(&self, events: Vec<Event>) {
-        *self.events.lock().unwrap() = events;
+        let mut state = self.state.lock().unwrap();
+        state.events = events;
+        state.generation += 1;
     }
 
     pub(crate) fn event(&self, idx: usize) -> Event {
-        self.events.lock().unwrap()[idx].clone()
+        self.state.lock().unwrap().events[idx].clone()
+    }
+
+    /// Number of schedule requests received.
+    pub(crate) fn request_count(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+
+    /// Number of schedule requests that were answered with "304 Not Modified".
+    pub(crate) fn not_modified_count(&self) -> usize {
+        self.state.lock().unwrap().not_modified
+    }
+
+    /// Respond to the next `count` schedule requests with "503 Service Unavailable".
+    pub(crate) fn fail_next(&self, count: usize) {
+        self.state.lock().unwrap().failures = count;
+    }
+
+    pub(crate) fn set_delay(&self, delay: Duration) {
+        self.state.lock().unwrap().delay = delay;
+    }
+
+    pub(crate) fn last_headers(&self) -> HeaderMap {
+        self.state.lock().unwrap().last_headers.clone()
     }
 
     pub(crate) async fn stop(&mut self) {
@@ -64,9 +126,34 @@ impl DummyScheduleServer {
     }
 }
 
-async fn schedule(State(state): State<Events>) -> Response {
-    let events = state.lock().unwrap().clone();
-    Json(events).into_response()
+async fn schedule(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let delay = {
+        let mut state = state.lock().unwrap();
+        state.requests += 1;
+        state.last_headers = headers.clone();
+        state.delay
+    };
+
+    tokio::time::sleep(delay).await;
+
+    let mut state = state.lock().unwrap();
+
+    if state.failures > 0 {
+        state.failures -= 1;
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let etag = format!("\"{}\"", state.generation);
+
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| v.as_bytes() == etag.as_bytes())
+    {
+        state.not_modified += 1;
+        return StatusCode::NOT_MODIFIED.into_response();
+    }
+
+    ([(header::ETAG, etag)], Json(state.events.clone())).into_response()
 }
 
 #[macro_export]
@@ -2,21 +2,26 @@ mod smol_event;
 
 use crate::smol_event::SmolEvent;
 use chrono::Duration as ChronoDuration;
+use chrono_tz::Tz;
 use clap::Parser;
 use emfcamp_schedule_api::{
     Client as ScheduleClient,
     announcer::{Announcer, AnnouncerPollResult, AnnouncerSettingsBuilder},
+    schedule::{
+        decode::Decoding,
+        mutation::{CanonicalVenues, MutatorSpec, Pipeline},
+        venue::VenueRegistry,
+    },
 };
 use metrics::{counter, describe_counter};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use rumqttc::{AsyncClient, LastWill, MqttOptions, QoS};
 use serde::Serialize;
-use std::{net::SocketAddr, time::Duration as StdDuration};
+use std::{net::SocketAddr, path::PathBuf, time::Duration as StdDuration};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
 const EVENT_ANNOUNCEMENTS_METRIC: &str = "mqtt_event_announcements";
-
 /// Announces the EMF schedule via DAPNET
 #[derive(Debug, Parser)]
 struct Cli {
@@ -28,6 +33,23 @@ struct Cli {
     )]
     api_url: Url,
 
+    /// Time in seconds after which a request to the schedule API is abandoned
+    #[arg(long, env, default_value = "10")]
+    api_timeout: u64,
+
+    /// Number of times a request to the schedule API that fails to connect or gets a server error
+    /// is retried
+    #[arg(long, env, default_value = "2")]
+    api_retries: u32,
+
+    /// Skip events that cannot be decoded instead of failing to refresh the schedule
+    #[arg(long, env)]
+    lenient: bool,
+
+    /// Timezone in which the schedule API gives times that have no offset
+    #[arg(long, env, default_value = "Europe/London")]
+    timezone: Tz,
+
     /// Time in seconds before the start time of an event to send the notification
     #[arg(long, env)]
     pre_event_announcement_time: i64,
@@ -56,6 +78,16 @@ struct Cli {
     #[arg(long, env)]
     mqtt_topic_prefix: String,
 
+    /// Mutators applied each time the schedule is refreshed, e.g. to only announce events at
+    /// certain venues (a JSON file, or TOML if the name ends in .toml)
+    #[arg(long, env)]
+    mutators: Option<PathBuf>,
+
+    /// Venue registry, so that announcements always use the same name for a venue whatever
+    /// alias the schedule gives it (a JSON file, or TOML if the name ends in .toml)
+    #[arg(long, env)]
+    venue_registry: Option<PathBuf>,
+
     /// Address on which to run the metrics endpoint
     #[arg(long, env, default_value = "127.0.0.1:9090")]
     observability_address: SocketAddr,
@@ -79,19 +111,46 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Setup schedule API client
-    let schedule_client = ScheduleClient::new(cli.api_url);
+    let schedule_client = ScheduleClient::builder(cli.api_url)
+        .application(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+        .timeout(StdDuration::from_secs(cli.api_timeout))
+        .retries(cli.api_retries)
+        .decoding(if cli.lenient {
+            Decoding::Lenient
+        } else {
+            Decoding::Strict
+        })
+        .timezone(cli.timezone)
+        .build()?;
 
     let event_start_offset = -ChronoDuration::try_seconds(cli.pre_event_announcement_time)
         .ok_or_else(|| anyhow::anyhow!("Invalid pre event announcement time"))?;
     info!("Event start offset: {:?}", event_start_offset);
 
+    let mut pipeline = match cli.mutators {
+        Some(path) => Pipeline::from_file(path)?,
+        None => Pipeline::default(),
+    };
+    if let Some(path) = cli.venue_registry {
+        let canonical_venues = CanonicalVenues::new(VenueRegistry::from_file(path)?);
+        pipeline
+            .mutators
+            .insert(0, MutatorSpec::CanonicalVenues(canonical_venues));
+    }
+    info!("Mutators: {:?}", pipeline.mutators);
+
     let mut announcer = Announcer::new(
         AnnouncerSettingsBuilder::default()
             .event_start_offset(event_start_offset)
+            .pipeline(pipeline)
             .build()?,
         schedule_client,
     )
     .await?;
+    info!(
+        "Events rejected from schedule: {}",
+        announcer.rejected_events().len()
+    );
 
     // Configure MQTT broker connection
     let online_topic = format!("{}/online", cli.mqtt_topic_prefix);
@@ -135,6 +194,12 @@ async fn main() -> anyhow::Result<()> {
                 return Ok(());
             }
             msg = announcer.poll() => {
+                if let Ok(AnnouncerPollResult::ScheduleRefreshed(changes)) = &msg {
+                    info!(
+                        "Schedule refreshed ({changes:?}, {} events rejected)",
+                        announcer.rejected_events().len()
+                    );
+                }
                 handle_announcer_event(&mqtt_client, &cli.mqtt_topic_prefix, msg).await;
             }
             event = mqtt_eventloop.poll() => {
@@ -20,10 +20,18 @@ pub(crate) struct SmolEvent {
     title: String,
 
     speaker: String,
+
+    /// Start of the description as plain text.
+    summary: String,
 }
 
+/// Maximum length, in characters, of the description summary.
+const SUMMARY_LENGTH: usize = 140;
+
 impl From<Event> for SmolEvent {
     fn from(event: Event) -> Self {
+        let summary = event.description_summary(SUMMARY_LENGTH);
+
         Self {
             id: event.id,
             kind: event.kind,
@@ -32,6 +40,7 @@ impl From<Event> for SmolEvent {
             venue: event.venue,
             title: event.title,
             speaker: event.speaker,
+            summary,
         }
     }
 }